@@ -0,0 +1,17 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use tmtc_system::Beacon;
+use tmtc_system_fuzz::*;
+
+fuzz_target!(|data: &[u8]| {
+    let mut beacon = FuzzBeacon::new();
+    if beacon.from_bytes(data).is_err() {
+        return;
+    }
+    // unused bitfield bits and trailing bytes are dropped when encoding,
+    // after that the encoding has to be stable
+    let encoded = beacon.to_bytes().unwrap().to_vec();
+    let mut copy = FuzzBeacon::new();
+    copy.from_bytes(&encoded).expect("encoded beacons parse");
+    assert_eq!(copy.to_bytes().unwrap(), &encoded[..]);
+});
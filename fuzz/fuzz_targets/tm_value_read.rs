@@ -0,0 +1,22 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use tmtc_system_fuzz::*;
+
+fuzz_target!(|data: &[u8]| {
+    check_read::<u8>(data);
+    check_read::<bool>(data);
+    check_read::<char>(data);
+    check_read::<i32>(data);
+    check_read::<f64>(data);
+    check_read::<u128>(data);
+    check_read::<[i16; 3]>(data);
+    check_read::<tmtc_system::BigEndian<[Option<u32>; 2]>>(data);
+    check_read::<Option<u64>>(data);
+    check_read::<[Option<u8>; 4]>(data);
+    check_read::<tmtc_system::heapless::Vec<u16, 4>>(data);
+    check_read::<tmtc_system::heapless::String<8>>(data);
+    check_read::<FuzzVector>(data);
+    check_read::<FuzzEnum>(data);
+    check_read::<FuzzPacked>(data);
+    check_read::<[FuzzEnum; 2]>(data);
+});
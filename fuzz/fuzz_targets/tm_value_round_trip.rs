@@ -0,0 +1,35 @@
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tmtc_system_fuzz::*;
+
+#[derive(Arbitrary, Debug)]
+struct Values {
+    primitive: i64,
+    float: f32,
+    array: [u16; 5],
+    option: Option<i8>,
+    vector: FuzzVector,
+    enumeration: FuzzEnum,
+    nested: [Option<FuzzEnum>; 2],
+    packed: FuzzPacked,
+    record: FuzzRecord,
+    records: [Option<FuzzRecord>; 2],
+}
+
+fuzz_target!(|values: Values| {
+    check_round_trip(&values.primitive);
+    check_round_trip(&values.float);
+    check_round_trip(&values.array);
+    check_round_trip(&values.option);
+    check_round_trip(&values.vector);
+    check_round_trip(&values.enumeration);
+    check_round_trip(&values.nested);
+    check_round_trip(&values.packed);
+    check_round_trip(&values.record.samples);
+    check_round_trip(&values.record.label);
+    check_round_trip(&values.record.temperature);
+    check_round_trip(&values.record.vector);
+    check_round_trip(&values.record);
+    check_round_trip(&values.records);
+});
@@ -0,0 +1,127 @@
+//! Types and properties shared by the fuzz targets, run them with `cargo fuzz run <target>`
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use arbitrary::Arbitrary;
+use tmtc_system::*;
+
+#[derive(TMValue, Arbitrary, Debug, Default, PartialEq, Clone, Copy)]
+pub struct FuzzVector {
+    pub x: i16,
+    pub y: f32,
+    pub z: Option<u64>,
+}
+
+#[derive(TMValue, Arbitrary, Debug, Default, PartialEq, Clone, Copy)]
+pub enum FuzzEnum {
+    #[default]
+    Empty,
+    Scalar(i8),
+    Vector(FuzzVector, [u16; 2]),
+}
+
+#[derive(TMValue, Debug, Default, PartialEq, Clone, Copy)]
+#[tm(packed)]
+pub struct FuzzPacked {
+    pub flag: bool,
+    #[tm(bits = 3)]
+    pub mode: u8,
+    #[tm(bits = 7)]
+    pub offset: i16,
+}
+// only values that fit into the bit widths round trip
+impl<'a> Arbitrary<'a> for FuzzPacked {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            flag: u.arbitrary()?,
+            mode: u.arbitrary::<u8>()? & 0b111,
+            offset: u.int_in_range(-64..=63)?,
+        })
+    }
+}
+
+/// Wrapper and bounded types inside a derived struct
+#[derive(TMValue, Debug, Default, PartialEq, Clone)]
+pub struct FuzzRecord {
+    pub packed: FuzzPacked,
+    pub samples: heapless::Vec<u16, 4>,
+    pub label: heapless::String<8>,
+    pub temperature: Scaled<i16, 100>,
+    pub counter: BigEndian<u32>,
+    pub vector: BigEndian<FuzzVector>,
+    pub offset: LittleEndian<i64>,
+}
+impl<'a> Arbitrary<'a> for FuzzRecord {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut samples = heapless::Vec::new();
+        for sample in u.arbitrary_iter::<u16>()?.take(4) {
+            samples.push(sample?).unwrap();
+        }
+        let mut label = heapless::String::new();
+        for c in u.arbitrary::<&str>()?.chars() {
+            if label.push(c).is_err() {
+                break;
+            }
+        }
+        Ok(Self {
+            packed: u.arbitrary()?,
+            samples,
+            label,
+            temperature: Scaled::from_raw(u.arbitrary()?),
+            counter: BigEndian(u.arbitrary()?),
+            vector: BigEndian(u.arbitrary()?),
+            offset: LittleEndian(u.arbitrary()?),
+        })
+    }
+}
+
+#[telemetry_definition(id = 0)]
+pub mod telemetry {
+    #[tmv(u32)]
+    struct Timestamp;
+    #[tmv(crate::FuzzVector)]
+    struct Vector;
+    #[tmv(crate::FuzzEnum)]
+    struct Mode;
+    #[tmv([Option<i32>; 3])]
+    struct Samples;
+}
+
+beacon!(
+    FuzzBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 1,
+    values(Vector, Mode, Samples)
+);
+
+/// Writes the value, reads it back and checks that both agree on the byte count
+/// and that the value survives the round trip
+pub fn check_round_trip<T: TMValue + PartialEq + core::fmt::Debug>(value: &T) {
+    let mut mem = vec![0u8; T::MAX_BYTE_SIZE];
+    let written = value
+        .write(&mut mem)
+        .expect("MAX_BYTE_SIZE fits every value");
+    let (read, copy) = T::read(&mem[..written]).expect("written values read back");
+    assert_eq!(read, written);
+    // NaN floats are never equal, compare their encoding instead
+    #[allow(clippy::eq_op)]
+    if value == value {
+        assert_eq!(*value, copy);
+    }
+    let mut again = vec![0u8; T::MAX_BYTE_SIZE];
+    let rewritten = copy.write(&mut again).unwrap();
+    assert_eq!(mem[..written], again[..rewritten]);
+}
+
+/// Reads arbitrary bytes, anything accepted has to be written back byte for byte
+pub fn check_read<T: TMValue>(bytes: &[u8]) {
+    if let Ok((read, value)) = T::read(bytes) {
+        assert!(read <= bytes.len());
+        let mut mem = vec![0u8; T::MAX_BYTE_SIZE];
+        let written = value
+            .write(&mut mem)
+            .expect("MAX_BYTE_SIZE fits every value");
+        assert_eq!(written, read);
+        assert_eq!(mem[..written], bytes[..read]);
+    }
+}
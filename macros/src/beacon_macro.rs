@@ -49,6 +49,27 @@ pub fn impl_macro(args: Punctuated<Meta, Token![,]>) -> TokenStream {
         panic!("args should contain tm definitions list");
     };
 
+    // Optional flags
+    let mut pus_housekeeping = false;
+    let mut crc_kind = quote! { Ccitt16 };
+    let mut crc_size: usize = 2;
+    let mut dictionary_hash = false;
+    for arg in args_iter {
+        let Meta::NameValue(option) = arg else {
+            panic!("optional beacon args should be name value pairs");
+        };
+        let name = option
+            .path
+            .get_ident()
+            .expect("optional beacon arg should be an identifier");
+        match name.to_string().as_str() {
+            "pus" => pus_housekeeping = parse_bool(&option.value),
+            "crc" => (crc_kind, crc_size) = parse_crc_kind(&option.value),
+            "dictionary_hash" => dictionary_hash = parse_bool(&option.value),
+            _ => panic!("unknown beacon arg {}", name),
+        }
+    }
+
     let tm_definitions: Vec<_> = tm_definitions_arg
         .parse_args_with(Punctuated::<Path, Token![,]>::parse_separated_nonempty)
         .expect("could not parse header list")
@@ -78,9 +99,9 @@ pub fn impl_macro(args: Punctuated<Meta, Token![,]>) -> TokenStream {
 
     let i: Vec<_> = (0..names.len()).collect();
 
-    let serializers = names.iter().zip(paths).map(|(name, path)| {
+    let serializers = names.iter().zip(paths.iter()).map(|(name, path)| {
         quote! {
-            if let Some(value) = self.#name {
+            if let Some(value) = &self.#name {
                 let mut serialized = value.serialize_ground(&#path, timestamp, serializer)?;
                 serialized_values.append(&mut serialized);
             }
@@ -98,6 +119,78 @@ pub fn impl_macro(args: Punctuated<Meta, Token![,]>) -> TokenStream {
     } else {
         quote! {}
     };
+    let deserializers = names
+        .iter()
+        .zip(paths.iter())
+        .zip(itd_paths.iter())
+        .map(|((name, path), itd_path)| {
+            quote! {
+                #itd_path::ADDRESS => {
+                    let (value_timestamp, value) =
+                        #itd_path::TMValueType::deserialize_ground(&#path, bytes.as_ref(), deserializer)
+                            .map_err(BeaconDeserializeError::Deserialize)?
+                            .into_parts();
+                    if timestamp.as_ref().is_some_and(|t| *t != value_timestamp) {
+                        return Err(BeaconDeserializeError::TimestampMismatch);
+                    }
+                    timestamp = Some(value_timestamp);
+                    beacon.#name = Some(value);
+                }
+            }
+        });
+    let deserializer_func = if cfg!(feature = "ground-deserialize") {
+        quote! {
+            /// Rebuilds a beacon from the pairs returned by [`Self::serialize`].
+            /// Calibrated and unknown addresses are skipped, all values have to
+            /// carry the same timestamp
+            pub fn deserialize<D, A, B>(
+                pairs: impl IntoIterator<Item = (A, B)>,
+                deserializer: &D,
+            ) -> Result<Self, BeaconDeserializeError<D::Error>>
+            where
+                D: Deserializer,
+                A: AsRef<str>,
+                B: AsRef<[u8]>,
+            {
+                let mut beacon = Self::new();
+                let mut timestamp = None;
+                for (address, bytes) in pairs {
+                    match address.as_ref() {
+                        #(#deserializers)*
+                        _ => {}
+                    }
+                }
+                if let Some(timestamp) = timestamp {
+                    beacon.timestamp = timestamp;
+                }
+                Ok(beacon)
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let schema_hash = if dictionary_hash {
+        quote! { .with_dictionary_hash(Self::DICTIONARY_HASH) }
+    } else {
+        quote! {}
+    };
+    let schema_func = if cfg!(feature = "ground") {
+        quote! {
+            /// Frame layout of this beacon, for decoding without the rust types
+            pub fn schema() -> BeaconSchema {
+                BeaconSchema::new(
+                    stringify!(#beacon_name),
+                    BEACON_ID,
+                    CRC,
+                    definition_schema::<#timestamp_path>(),
+                    [#(definition_schema::<#paths>()),*],
+                )
+                #schema_hash
+            }
+        }
+    } else {
+        quote! {}
+    };
     let serializer_imports = if cfg!(feature = "ground") {
         quote! {
             use alloc::vec::Vec;
@@ -107,7 +200,102 @@ pub fn impl_macro(args: Punctuated<Meta, Token![,]>) -> TokenStream {
     };
 
     let bitfield_size: usize = (names.len() as f32 / 8.).ceil() as usize;
-    let header_size: usize = 1 + 2 + bitfield_size; // id + crc + bitfield
+    let crc_end: usize = 1 + crc_size;
+    let hash_size: usize = if dictionary_hash { 4 } else { 0 };
+    let bitfield_start: usize = crc_end + hash_size;
+    let header_size: usize = bitfield_start + bitfield_size; // id + crc + hash + bitfield
+
+    let (hash_const, hash_check, hash_write) = if dictionary_hash {
+        (
+            quote! {
+                impl #beacon_name {
+                    /// Folded hash of the beacon layout and the definitions it sends, carried
+                    /// in the header. Frames built from a different dictionary are rejected
+                    pub const DICTIONARY_HASH: u32 = fold_hash(layout_hash(
+                        "Beacon",
+                        &[
+                            BEACON_ID as u64,
+                            str_hash(stringify!(#crc_kind)),
+                            <#timestamp_path as InternalTelemetryDefinition>::HASH,
+                            #(#itd_paths::HASH),*
+                        ],
+                    ));
+                }
+            },
+            quote! {
+                // Dictionary hash
+                let found = u32::from_le_bytes(bytes[#crc_end..#bitfield_start].try_into().unwrap());
+                if found != Self::DICTIONARY_HASH {
+                    return Err(ParseError::DictionaryMismatch {
+                        expected: Self::DICTIONARY_HASH,
+                        found,
+                    });
+                }
+            },
+            quote! {
+                // Dictionary hash
+                self.storage[#crc_end..#bitfield_start].copy_from_slice(&Self::DICTIONARY_HASH.to_le_bytes());
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {})
+    };
+
+    let (housekeeping_field, housekeeping_init, housekeeping_funcs) = if pus_housekeeping {
+        (
+            quote! {
+                pub housekeeping: pus::Housekeeping,
+            },
+            quote! {
+                housekeeping: pus::Housekeeping::new(BEACON_ID as u16),
+            },
+            quote! {
+                /// Writes the packet data field of a TM[3,25] housekeeping parameter report
+                /// generated at `time`. The application data holds the structure id followed
+                /// by every value in big endian, see [`pus`].
+                /// The message counter only advances once the report was written
+                pub fn housekeeping_report(
+                    &mut self,
+                    destination_id: u16,
+                    time: &pus::CucTime,
+                    mem: &mut [u8],
+                ) -> Result<usize, PusError> {
+                    let mut pos = pus::PusTmSecondaryHeader {
+                        service: pus::HOUSEKEEPING_SERVICE,
+                        subtype: pus::HOUSEKEEPING_PARAMETER_REPORT,
+                        message_counter: self.housekeeping.message_counter(),
+                        destination_id,
+                    }
+                    .write(time, mem)?;
+                    // Structure id
+                    mem.get_mut(pos..pos + 2)
+                        .ok_or(PusError::OutOfMemory)?
+                        .copy_from_slice(&self.housekeeping.structure_id().to_be_bytes());
+                    pos += 2;
+                    // Parameters
+                    #(
+                        let value = self.#names
+                            .as_ref()
+                            .ok_or(PusError::MissingParameter(#itd_paths::ADDRESS))?;
+                        pos += value.write_be(mem.get_mut(pos..).ok_or(PusError::OutOfMemory)?)?;
+                    )*
+                    self.housekeeping.next_message_counter();
+                    Ok(pos)
+                }
+                /// Handles the packet data field of a TC[3,5], TC[3,6] or TC[3,31] request.
+                /// Returns whether the request referenced this beacon
+                pub fn handle_housekeeping_request(&mut self, data: &[u8]) -> Result<bool, PusError> {
+                    let header = pus::PusTcSecondaryHeader::read(data)?;
+                    if header.service != pus::HOUSEKEEPING_SERVICE {
+                        return Err(PusError::UnsupportedService(header.service));
+                    }
+                    self.housekeeping.handle_request(header.subtype, &data[pus::PusTcSecondaryHeader::SIZE..])
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {})
+    };
 
     quote! {
         pub use #beacon_module_name::#beacon_name;
@@ -115,9 +303,12 @@ pub fn impl_macro(args: Punctuated<Meta, Token![,]>) -> TokenStream {
             use tmtc_system::{_internal::*, *};
             #serializer_imports
             pub const BEACON_ID: u8 = #id;
+            pub const CRC: crc::CrcKind = crc::CrcKind::#crc_kind;
+            #hash_const
             pub struct #beacon_name {
                 storage: [u8; Self::BYTE_SIZE],
                 pub timestamp: #timestamp_type,
+                #housekeeping_field
                 #(pub #names: Option<#itd_paths::TMValueType>),*
             }
             impl #beacon_name {
@@ -129,72 +320,102 @@ pub fn impl_macro(args: Punctuated<Meta, Token![,]>) -> TokenStream {
                     Self {
                         storage: [0u8; Self::BYTE_SIZE],
                         timestamp: #timestamp_type::default(),
+                        #housekeeping_init
                         #(#names: None),*
                     }
                 }
                 #serializer_func
+                #deserializer_func
+                #schema_func
+                #housekeeping_funcs
             }
             impl Beacon for #beacon_name {
                 type Timestamp = #timestamp_type;
-                fn from_bytes(&mut self, bytes: &[u8], crc_func: &mut dyn FnMut(&[u8]) -> u16) -> Result<(), ParseError> {
+                fn from_bytes(&mut self, bytes: &[u8]) -> Result<(), ParseError> {
                     if bytes.len() < #header_size {
-                        return Err(ParseError::OutOfMemory);
+                        return Err(ParseError::OutOfMemory {
+                            expected: #header_size,
+                            available: bytes.len(),
+                        });
                     }
                     // Beacon ID
                     if bytes[0] != BEACON_ID {
-                        return Err(ParseError::WrongId);
+                        return Err(ParseError::WrongId {
+                            expected: BEACON_ID as u16,
+                            found: bytes[0] as u16,
+                        });
                     }
                     // Crc
-                    let received_crc = u16::from_le_bytes(bytes[1..3].try_into().unwrap());
-                    let calculated_crc = (crc_func)(&bytes[3..]);
+                    let received_crc = CRC.read(&bytes[1..#crc_end]);
+                    let calculated_crc = CRC.checksum(&bytes[#crc_end..]);
                     if calculated_crc != received_crc {
-                        return Err(ParseError::BadCRC);
+                        return Err(ParseError::BadCRC {
+                            received: received_crc,
+                            calculated: calculated_crc,
+                        });
                     }
+                    #hash_check
                     let mut pos = #header_size;
                     // Bitfield
-                    let bitfield = Bitfield::<#bitfield_size>::new_from_bytes(bytes[3..#header_size].try_into().unwrap());
+                    let bitfield = Bitfield::<#bitfield_size>::new_from_bytes(bytes[#bitfield_start..#header_size].try_into().unwrap());
                     // Timestamp
-                    let (len, timestamp_value) = #timestamp_type::read(&bytes[pos..]).map_err(|_| ParseError::OutOfMemory)?;
+                    let (len, timestamp_value) = read_definition::<#timestamp_path>(bytes, pos)?;
                     pos += len;
-                    self.timestamp = timestamp_value;
                     // Parsers
                     #(
-                        if bitfield.get(#i) {
-                            let (len, value) = #itd_paths::TMValueType::read(&bytes[pos..]).map_err(|_| ParseError::OutOfMemory)?;
+                        let #names = if bitfield.get(#i) {
+                            let (len, value) = read_definition::<#paths>(bytes, pos)?;
                             pos += len;
-                            self.#names = Some(value);
+                            Some(value)
                         } else {
-                            self.#names = None;
-                        }
+                            None
+                        };
                     )*
+                    // Only update the beacon once the whole frame was parsed
+                    self.timestamp = timestamp_value;
+                    #(self.#names = #names;)*
                     Ok(())
                 }
-                fn to_bytes(&mut self, crc_func: &mut dyn FnMut(&[u8]) -> u16) -> &[u8] {
+                fn to_bytes(&mut self) -> Result<&[u8], BeaconOperationError> {
                     // Beacon ID
                     self.storage[0] = BEACON_ID;
                     let mut pos = #header_size;
                     // Bitfield
                     let mut bitfield = Bitfield::<#bitfield_size>::new();
-                    // Timestamp
-                    pos += self.timestamp.write(&mut self.storage[pos..]).unwrap();
+                    // Timestamp, the storage fits every value so only invalid values fail
+                    pos += self.timestamp.write(&mut self.storage[pos..]).map_err(|error| {
+                        BeaconOperationError::InvalidValue {
+                            address: <#timestamp_path as InternalTelemetryDefinition>::ADDRESS,
+                            error,
+                        }
+                    })?;
                     // Parsers
                     #(
-                        if let Some(value) = self.#names {
-                            pos += value.write(&mut self.storage[pos..]).unwrap();
+                        if let Some(value) = &self.#names {
+                            pos += value.write(&mut self.storage[pos..]).map_err(|error| {
+                                BeaconOperationError::InvalidValue {
+                                    address: #itd_paths::ADDRESS,
+                                    error,
+                                }
+                            })?;
                             bitfield.set(#i);
                         }
                     )*
 
                     // Store Bitfield
-                    self.storage[3..#header_size].copy_from_slice(bitfield.bytes());
+                    self.storage[#bitfield_start..#header_size].copy_from_slice(bitfield.bytes());
+                    #hash_write
                     // Crc
-                    let crc = (crc_func)(&self.storage[3..pos]);
-                    self.storage[1..3].copy_from_slice(&crc.to_le_bytes());
-                    &self.storage[..pos]
+                    let (crc, checked) = self.storage[1..pos].split_at_mut(#crc_size);
+                    CRC.write(checked, crc);
+                    Ok(&self.storage[..pos])
                 }
                 fn set_timestamp(&mut self, timestamp: Self::Timestamp) {
                     self.timestamp = timestamp;
                 }
+                fn timestamp(&self) -> &Self::Timestamp {
+                    &self.timestamp
+                }
                 fn insert_slice(&mut self, telemetry_definition: &dyn TelemetryDefinition, bytes: &[u8]) -> Result<(), BeaconOperationError> {
                     match telemetry_definition.id() {
                         #(
@@ -220,3 +441,35 @@ pub fn impl_macro(args: Punctuated<Meta, Token![,]>) -> TokenStream {
         }
     }
 }
+
+fn parse_bool(value: &syn::Expr) -> bool {
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Bool(flag),
+        ..
+    }) = value
+    else {
+        panic!("expected a boolean flag");
+    };
+    flag.value
+}
+
+fn parse_crc_kind(value: &syn::Expr) -> (TokenStream, usize) {
+    let syn::Expr::Path(path) = value else {
+        panic!("expected a crc kind");
+    };
+    match path
+        .path
+        .get_ident()
+        .expect("expected a crc kind")
+        .to_string()
+        .as_str()
+    {
+        "ccitt16" => (quote! { Ccitt16 }, 2),
+        "x25" => (quote! { X25 }, 2),
+        "crc32c" => (quote! { Crc32c }, 4),
+        other => panic!(
+            "unsupported crc kind {}, expected ccitt16, x25 or crc32c",
+            other
+        ),
+    }
+}
@@ -1,6 +1,7 @@
 #![feature(iter_intersperse)]
 mod beacon_macro;
 mod macro_utils;
+mod tc_definition_macro_attribute;
 mod tm_definition_macro_attribute;
 mod tm_value_macro_derive;
 use std::panic;
@@ -9,7 +10,10 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{Meta, MetaNameValue, Token, parse_macro_input, parse2, punctuated::Punctuated};
 
-#[proc_macro_derive(TMValue)]
+/// Byte aligned by default. `#[tm(packed)]` structs pack their `bool` and integer
+/// fields into `#[tm(bits = N)]` bits each (default: the full type width),
+/// most significant bit first unless `#[tm(packed, bit_order = lsb)]` is given
+#[proc_macro_derive(TMValue, attributes(tm))]
 pub fn tm_value(item: TokenStream) -> TokenStream {
     let ast = syn::parse(item).unwrap();
 
@@ -30,6 +34,25 @@ pub fn beacon(input: TokenStream) -> TokenStream {
 pub fn telemetry_definition(attr: TokenStream, item: TokenStream) -> TokenStream {
     let ast = syn::parse(item).unwrap();
     let name_value_pairs = parse_macro_input!(attr with Punctuated<MetaNameValue, Token![,]>::parse_separated_nonempty);
+    let (id, tmtc_system_address) = parse_definition_attrs(&name_value_pairs);
+
+    // Build the telemetry definition recursive module
+    tm_definition_macro_attribute::impl_macro(ast, id, tmtc_system_address).into()
+}
+
+#[proc_macro_attribute]
+pub fn telecommand_definition(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ast = syn::parse(item).unwrap();
+    let name_value_pairs = parse_macro_input!(attr with Punctuated<MetaNameValue, Token![,]>::parse_separated_nonempty);
+    let (id, tmtc_system_address) = parse_definition_attrs(&name_value_pairs);
+
+    // Build the telecommand definition recursive module
+    tc_definition_macro_attribute::impl_macro(ast, id, tmtc_system_address).into()
+}
+
+fn parse_definition_attrs(
+    name_value_pairs: &Punctuated<MetaNameValue, Token![,]>,
+) -> (u16, syn::Path) {
     if name_value_pairs
         .get(0)
         .expect("missing id")
@@ -66,7 +89,5 @@ pub fn telemetry_definition(attr: TokenStream, item: TokenStream) -> TokenStream
     } else {
         parse2(quote! { tmtc_system }).unwrap()
     };
-
-    // Build the telemetry definition recursive module
-    tm_definition_macro_attribute::impl_macro(ast, id, tmtc_system_address).into()
+    (id, tmtc_system_address)
 }
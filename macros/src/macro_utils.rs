@@ -1,24 +1,285 @@
-use proc_macro2::TokenStream;
+use std::array::from_fn;
+use std::iter::{once, zip};
+
+use heck::ToSnakeCase;
+use proc_macro2::{Ident, TokenStream};
 use quote::{ToTokens, quote};
-use syn::Type;
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    Attribute, Item, ItemMod, ItemStruct, Meta, MetaNameValue, Token, Type, punctuated::Punctuated,
+};
 
 pub fn parse_type_path(ty: &Type) -> TokenStream {
     match ty {
         Type::Path(syn::TypePath { path, .. }) => path
-            .segments
-            .iter()
-            .map(|s| {
-                let ident = &s.ident;
-                match &s.arguments {
-                    syn::PathArguments::None => s.ident.to_token_stream(),
-                    syn::PathArguments::AngleBracketed(args) => quote! {#ident::#args},
-                    syn::PathArguments::Parenthesized(_) => {
-                        panic!("Parenthesized types are unsupported")
-                    }
-                }
-            })
+            .leading_colon
+            .map(|c| c.to_token_stream())
+            .into_iter()
+            .chain(
+                path.segments
+                    .iter()
+                    .map(|s| {
+                        let ident = &s.ident;
+                        match &s.arguments {
+                            syn::PathArguments::None => s.ident.to_token_stream(),
+                            syn::PathArguments::AngleBracketed(args) => quote! {#ident::#args},
+                            syn::PathArguments::Parenthesized(_) => {
+                                panic!("Parenthesized types are unsupported")
+                            }
+                        }
+                    })
+                    .intersperse(quote!(::)),
+            )
             .collect(),
         Type::Array(a) => quote! {<#a>},
+        Type::Tuple(t) => quote! {<#t>},
         _ => panic!("unsupported type"),
     }
 }
+
+/// Parses the optional `id` of a module attribute like `#[tmm(id = 100)]`
+pub fn parse_module_id(attrs: &[Attribute], macro_name: &str) -> Option<u16> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(macro_name))
+        .map(|v| {
+            v.parse_args_with(Punctuated::<Meta, Token![,]>::parse_separated_nonempty)
+                .expect(&format!(
+                    "Could not parse {} attribute parameters",
+                    macro_name
+                ))
+                .iter()
+                .filter_map(|m| m.require_name_value().ok())
+                .filter(|m| m.path.get_ident().filter(|p| *p == "id").is_some())
+                .map(|m| {
+                    if let syn::Expr::Lit(value) = &m.value {
+                        value
+                    } else {
+                        panic!("unexpected attribute value type")
+                    }
+                })
+                .map(|m| {
+                    if let syn::Lit::Int(value) = &m.lit {
+                        value
+                    } else {
+                        panic!("unexpected attribute value type")
+                    }
+                })
+                .next()
+                .map(|lit| lit.base10_parse().unwrap())
+        })
+        .flatten()
+}
+
+/// Const block evaluating to the largest of the given byte sizes
+pub fn max_byte_size(byte_lengths: &TokenStream) -> TokenStream {
+    quote! {
+        {
+            let SIZES = [#byte_lengths];
+            let mut max = 0;
+            let mut i = 0;
+            while i < SIZES.len() {
+                if SIZES[i] > max {
+                    max = SIZES[i];
+                }
+                i += 1;
+            }
+            max
+        }
+    }
+}
+
+/// Arguments of a definition attribute like `#[tmv(u32, endian = big)]`:
+/// the value type followed by optional name value pairs
+pub struct ValueMacroInput {
+    pub ty: Type,
+    pub metas: Punctuated<MetaNameValue, Token![,]>,
+}
+
+impl Parse for ValueMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Parse first argument as a Type
+        let ty: Type = input.parse()?;
+
+        // If there's nothing else, return early
+        if input.is_empty() {
+            return Ok(Self {
+                ty,
+                metas: Punctuated::new(),
+            });
+        }
+
+        // Expect comma after type
+        input.parse::<Token![,]>()?;
+
+        // Parse remaining key-value pairs
+        let metas = Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)?;
+
+        Ok(Self { ty, metas })
+    }
+}
+
+/// A definition struct of a telemetry or telecommand tree
+pub struct Definition<'a> {
+    pub ident: &'a Ident,
+    pub args: ValueMacroInput,
+    /// Rust path of the struct, relative to the root module
+    pub path: TokenStream,
+    pub id: u16,
+    /// Dotted address, starting with the root module
+    pub address: String,
+}
+
+/// Generated code of a (sub)tree
+pub struct Tree<const N: usize> {
+    pub content: TokenStream,
+    pub id_getters: TokenStream,
+    pub address_getters: TokenStream,
+    pub byte_lengths: TokenStream,
+    /// Streams specific to the kind of tree, merged over all definitions
+    pub streams: [TokenStream; N],
+}
+
+/// Definition tree macro like `telemetry_definition`.
+/// The module walk, id assignment and lookup functions are shared,
+/// implementors only generate their specific code
+pub trait DefinitionTree<const N: usize> {
+    /// Attribute holding the value type of a definition, e.g. `tmv`
+    const VALUE_MACRO_NAME: &'static str;
+    /// Attribute holding the optional id of a module, e.g. `tmm`
+    const MODULE_MACRO_NAME: &'static str;
+
+    /// Items of a definition struct and its contribution to the streams
+    fn definition(&self, definition: Definition) -> (TokenStream, [TokenStream; N]);
+    /// Additional items of a submodule and the streams passed on to its parent
+    fn module(&self, module: &Ident, streams: [TokenStream; N]) -> (TokenStream, [TokenStream; N]);
+
+    fn generate_tree(&self, address: Vec<Ident>, id: &mut u16, items: &[Item]) -> Tree<N> {
+        let mut tree = Tree {
+            content: TokenStream::new(),
+            id_getters: TokenStream::new(),
+            address_getters: TokenStream::new(),
+            byte_lengths: TokenStream::new(),
+            streams: from_fn(|_| TokenStream::new()),
+        };
+        for item in items {
+            let src = match item {
+                Item::Struct(v) => self.generate_struct(&address, id, v),
+                Item::Mod(v) => self.generate_module_recursive(&address, id, v),
+                _ => panic!("module should only contain other modules and structs"),
+            };
+            tree.content.extend(src.content);
+            tree.id_getters.extend(src.id_getters);
+            tree.address_getters.extend(src.address_getters);
+            tree.byte_lengths.extend(src.byte_lengths);
+            for (acc, src) in zip(&mut tree.streams, src.streams) {
+                acc.extend(src);
+            }
+        }
+        tree
+    }
+
+    fn generate_struct(&self, address: &[Ident], id: &mut u16, v: &ItemStruct) -> Tree<N> {
+        // Parse value attribute
+        let args: ValueMacroInput = v
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident(Self::VALUE_MACRO_NAME))
+            .expect(&format!(
+                "Struct {} has no {} attribute",
+                &v.ident,
+                Self::VALUE_MACRO_NAME
+            ))
+            .parse_args()
+            .expect(&format!(
+                "Could not parse {} attribute parameters",
+                Self::VALUE_MACRO_NAME
+            ));
+
+        // this definitions name
+        let def = &v.ident;
+        // Parse rust address of the struct inside the module tree
+        let def_addr: TokenStream = address
+            .iter()
+            .skip(1)
+            .chain(once(def))
+            .map(|i| i.to_token_stream())
+            .intersperse(quote!(::))
+            .collect();
+        // Increment id
+        let def_id = *id;
+        *id += 1;
+        // calculate string address based on module tree
+        let str_base_addr: String = address
+            .iter()
+            .map(|i| i.to_string())
+            .intersperse(String::from("."))
+            .collect();
+        // Parse address
+        let str_address = format!("{}.{}", str_base_addr, def.to_string().to_snake_case());
+
+        let (content, streams) = self.definition(Definition {
+            ident: def,
+            args,
+            path: def_addr.clone(),
+            id: def_id,
+            address: str_address.clone(),
+        });
+        Tree {
+            content,
+            id_getters: quote! {
+                #def_id => Ok(&#def_addr),
+            },
+            address_getters: quote! {
+                #str_address => Ok(&#def_addr),
+            },
+            byte_lengths: quote! {
+                #def::MAX_BYTE_SIZE,
+            },
+            streams,
+        }
+    }
+
+    fn generate_module_recursive(&self, address: &[Ident], id: &mut u16, v: &ItemMod) -> Tree<N> {
+        // Parse module attribute
+        if let Some(module_id) = parse_module_id(&v.attrs, Self::MODULE_MACRO_NAME) {
+            if *id > module_id {
+                panic!("like schedules, ids should only move in one direction");
+            }
+            *id = module_id;
+        }
+        let start_id = *id;
+
+        let module_name = &v.ident;
+        let mut address = address.to_vec();
+        address.push(module_name.clone());
+        let tree = self.generate_tree(
+            address,
+            id,
+            &v.content.as_ref().expect("module sould not be empty").1,
+        );
+        let max_byte_size = max_byte_size(&tree.byte_lengths);
+        let (module_items, streams) = self.module(module_name, tree.streams);
+        let module_content = tree.content;
+
+        Tree {
+            content: quote! {
+                pub mod #module_name {
+                    use super::*;
+                    pub const fn id_range() -> (u16, u16) {
+                        (#start_id, #id)
+                    }
+                    pub const MAX_BYTE_SIZE: usize = #max_byte_size;
+                    #module_items
+                    #module_content
+                }
+            },
+            id_getters: tree.id_getters,
+            address_getters: tree.address_getters,
+            byte_lengths: quote! {
+                #module_name::MAX_BYTE_SIZE,
+            },
+            streams,
+        }
+    }
+}
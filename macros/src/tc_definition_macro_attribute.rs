@@ -0,0 +1,145 @@
+use crate::macro_utils::{Definition, DefinitionTree, max_byte_size};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+/// Generates the `telecommand_definition` tree
+struct TelecommandTree;
+
+/// Streams: dispatch match arms and handler bounds
+impl DefinitionTree<2> for TelecommandTree {
+    const VALUE_MACRO_NAME: &'static str = "tcv";
+    const MODULE_MACRO_NAME: &'static str = "tcm";
+
+    fn definition(&self, definition: Definition) -> (TokenStream, [TokenStream; 2]) {
+        let Definition {
+            ident: def,
+            args,
+            path: def_addr,
+            id: tc_id,
+            address,
+        } = definition;
+        if !args.metas.is_empty() {
+            panic!("{} only takes the argument type", Self::VALUE_MACRO_NAME);
+        }
+        let tcty = args.ty;
+
+        // generated documentation
+        let doc = format!(
+            "
+telecommand address: {},
+command id: {}",
+            address, tc_id
+        );
+
+        (
+            quote! {
+                #[doc = #doc]
+                pub struct #def;
+                impl InternalTelecommandDefinition for #def {
+                    type TCValueType = #tcty;
+                    const ID: u16 = #tc_id;
+                    const ADDRESS: &'static str = #address;
+                }
+                impl const TelecommandDefinition for #def {
+                    fn id(&self) -> u16 { Self::ID }
+                    fn address(&self) -> &str { #address }
+                }
+            },
+            [
+                quote! {
+                    #tc_id => {
+                        let (len, value) = <#def_addr as InternalTelecommandDefinition>::TCValueType::read(args)
+                            .map_err(DispatchError::BadArguments)?;
+                        if len != args.len() {
+                            return Err(DispatchError::TrailingBytes(args.len() - len));
+                        }
+                        TelecommandHandler::<#def_addr>::handle(handler, value)
+                            .map_err(DispatchError::ExecutionFailed)?;
+                    },
+                },
+                quote! {
+                    TelecommandHandler<#def_addr> +
+                },
+            ],
+        )
+    }
+
+    fn module(
+        &self,
+        _module: &Ident,
+        streams: [TokenStream; 2],
+    ) -> (TokenStream, [TokenStream; 2]) {
+        (TokenStream::new(), streams)
+    }
+}
+
+pub fn impl_macro(ast: syn::Item, mut id: u16, tmtc_system_address: syn::Path) -> TokenStream {
+    let syn::Item::Mod(telecommand_definition) = ast else {
+        panic!("telecommand defintion is not a module");
+    };
+
+    let root_mod_ident = telecommand_definition.ident;
+    let Some(root_mod_content) = telecommand_definition.content else {
+        panic!("module is empty");
+    };
+    let start_id = id;
+    let id_ref = &mut id;
+
+    let tree =
+        TelecommandTree.generate_tree(vec![root_mod_ident.clone()], id_ref, &root_mod_content.1);
+    let max_byte_size = max_byte_size(&tree.byte_lengths);
+    let (module_content, id_getters, address_getters) =
+        (tree.content, tree.id_getters, tree.address_getters);
+    let [dispatch_arms, handler_bounds] = tree.streams;
+
+    quote! {
+        pub mod #root_mod_ident {
+            use #tmtc_system_address::{
+                TelecommandDefinition, TelecommandDispatcher, TelecommandHandler, TelecommandHeader, TMValue,
+                DispatchError, _internal::*, NotFoundError
+            };
+            pub const fn from_id(id: u16) -> Result<&'static dyn TelecommandDefinition, NotFoundError> {
+                match id {
+                    #id_getters
+                    _ => Err(NotFoundError)
+                }
+            }
+            pub const fn from_address(address: &str) -> Result<&'static dyn TelecommandDefinition, NotFoundError> {
+                match address {
+                    #address_getters
+                    _ => Err(NotFoundError)
+                }
+            }
+            pub const fn id_range() -> (u16, u16) {
+                (#start_id, #id_ref)
+            }
+            pub const MAX_BYTE_SIZE: usize = #max_byte_size;
+            /// Routes telecommand frames of this tree to the matching handler
+            pub struct Dispatcher;
+            impl<H> TelecommandDispatcher<H> for Dispatcher
+            where
+                H: #handler_bounds
+            {
+                fn dispatch(
+                    &self,
+                    frame: &[u8],
+                    handler: &mut H,
+                ) -> Result<TelecommandHeader, (Option<TelecommandHeader>, DispatchError)> {
+                    let (pos, header) =
+                        TelecommandHeader::read(frame).map_err(|_| (None, DispatchError::OutOfMemory))?;
+                    let args = &frame[pos..];
+                    let mut dispatch = || {
+                        match header.id {
+                            #dispatch_arms
+                            id => return Err(DispatchError::UnknownId(id)),
+                        }
+                        Ok(())
+                    };
+                    dispatch().map_err(|error| (Some(header), error))?;
+                    Ok(header)
+                }
+            }
+            #module_content
+        }
+    }
+}
@@ -1,261 +1,167 @@
-use std::array::from_fn;
-use std::iter::{once, zip};
-
-use heck::ToSnakeCase;
-use proc_macro2::TokenStream;
+use crate::macro_utils::{Definition, DefinitionTree, max_byte_size};
+use proc_macro2::{Ident, TokenStream};
 use quote::{ToTokens, quote};
-use syn::parse::{Parse, ParseStream};
-use syn::{Item, Meta, Token, punctuated::Punctuated};
-use syn::{MetaNameValue, Type};
-
-const TM_VALUE_MACRO_NAME: &str = "tmv";
-const TM_MODULE_MACRO_NAME: &str = "tmm";
-
-struct TmValueMacroInput {
-    pub ty: Type,
-    pub metas: Punctuated<MetaNameValue, Token![,]>,
-}
-
-impl Parse for TmValueMacroInput {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        // Parse first argument as a Type
-        let ty: Type = input.parse()?;
-
-        // If there's nothing else, return early
-        if input.is_empty() {
-            return Ok(Self {
-                ty,
-                metas: Punctuated::new(),
-            });
+use syn::parse::Parser;
+use syn::{Token, Type, parse_quote, punctuated::Punctuated};
+
+/// Generates the `telemetry_definition` tree
+struct TelemetryTree;
+
+/// Streams: definition schemas, definition hashes and definition paths
+impl DefinitionTree<3> for TelemetryTree {
+    const VALUE_MACRO_NAME: &'static str = "tmv";
+    const MODULE_MACRO_NAME: &'static str = "tmm";
+
+    fn definition(&self, definition: Definition) -> (TokenStream, [TokenStream; 3]) {
+        let Definition {
+            ident: def,
+            args,
+            path: def_addr,
+            id: tm_id,
+            address,
+        } = definition;
+
+        // `endian = big` wraps the type, every other pair is a calibration
+        let (endian, calibrations): (Vec<_>, Vec<_>) = args
+            .metas
+            .into_iter()
+            .partition(|v| v.path.is_ident("endian"));
+        let ty = args.ty;
+        let tmty: Type = match endian
+            .first()
+            .map(|v| v.value.to_token_stream().to_string())
+        {
+            None => ty,
+            Some(endian) if endian == "big" => parse_quote! { BigEndian<#ty> },
+            Some(endian) if endian == "little" => parse_quote! { LittleEndian<#ty> },
+            Some(_) => panic!("endian should be big or little"),
+        };
+
+        let (address_endings, funcs): (Vec<_>, Vec<_>) =
+            calibrations.into_iter().map(|v| (v.path, v.value)).unzip();
+
+        // generated documentation
+        let mut calibrated = String::new();
+        for (i, addr) in address_endings.iter().enumerate() {
+            let doc = format!("{}, {} \n", i, &addr.to_token_stream().to_string());
+            calibrated.push_str(&doc);
         }
-
-        // Expect comma after type
-        input.parse::<Token![,]>()?;
-
-        // Parse remaining key-value pairs
-        let metas = Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)?;
-
-        Ok(Self { ty, metas })
-    }
-}
-
-fn generate_struct(
-    address: &Vec<syn::Ident>,
-    id: &mut u16,
-    v: &syn::ItemStruct,
-) -> [TokenStream; 4] {
-    // Parse "tmv" attribute
-    let args: TmValueMacroInput = v
-        .attrs
-        .iter()
-        .find(|attr| attr.path().is_ident(TM_VALUE_MACRO_NAME))
-        .expect(&format!(
-            "Struct {} has no {} attribute",
-            &v.ident, TM_VALUE_MACRO_NAME
-        ))
-        .parse_args()
-        .expect(&format!(
-            "Could not parse {} attribute parameters",
-            TM_VALUE_MACRO_NAME
-        ));
-
-    let tmty: Type = args.ty;
-
-    let (address_endings, funcs): (Vec<_>, Vec<_>) =
-        args.metas.into_iter().map(|v| (v.path, v.value)).unzip();
-
-    // this definitions name
-    let def = &v.ident;
-    // Parse rust address of the struct inside the telemetry module tree
-    let def_addr: TokenStream = address
-        .iter()
-        .skip(1)
-        .chain(once(def))
-        .map(|i| i.to_token_stream())
-        .intersperse(quote!(::))
-        .collect();
-    // Parse type of the TMValue the struct references
-    // Increment id
-    let tm_id = *id;
-    *id += 1;
-    // calculate string address based on module tree
-    let str_base_addr: String = address
-        .iter()
-        .map(|i| i.to_string())
-        .intersperse(String::from("."))
-        .collect();
-    // Parse address
-    let address = format!("{}.{}", str_base_addr, def.to_string().to_snake_case());
-    // generated documentation
-    let mut calibrated = String::new();
-    for (i, addr) in address_endings.iter().enumerate() {
-        let doc = format!("{}, {} \n", i, &addr.to_token_stream().to_string());
-        calibrated.push_str(&doc);
-    }
-    let doc = format!(
-        "
+        let doc = format!(
+            "
 telemetry address: {},
 can id: {},
 calibrated address endings:
 {}",
-        address, tm_id, &calibrated
-    );
-
-    // Serializer func
-    let serializer_func = if cfg!(feature = "ground") {
-        quote! {
-            impl SerializableTMValue<#def> for #tmty {
-                fn serialize_ground<T, S>(self, _def: &#def, timestamp: T, serializer: &S)
-                    -> Result<alloc::vec::Vec<(&'static str, alloc::vec::Vec<u8>)>, S::Error>
-                    where T: serde::Serialize + Clone + Copy,
-                          S: Serializer
-                {
-                    let mut serialized_pairs = alloc::vec::Vec::new();
-                    #({
-                        let nats_value = GroundTelemetry::new(timestamp, (#funcs)(&self));
-                        let bytes = serializer.serialize_value(&nats_value)?;
-                        serialized_pairs.push((concat!(#address, ".", stringify!(#address_endings)), bytes));
-                    })*
-
-                    let raw_nats_value = GroundTelemetry::new(timestamp, self);
-                    let raw_bytes = serializer.serialize_value(&raw_nats_value)?;
-                    serialized_pairs.push((#address, raw_bytes));
-
-                    Ok(serialized_pairs)
+            address, tm_id, &calibrated
+        );
+
+        // Serializer and deserializer funcs
+        // deserializing is opt-in, it needs serde::Deserialize on every value type
+        let deserializer_impl = if cfg!(feature = "ground-deserialize") {
+            quote! {
+                impl DeserializableTMValue<#def> for #tmty {
+                    fn deserialize_ground<T, D>(_def: &#def, bytes: &[u8], deserializer: &D)
+                        -> Result<GroundTelemetry<T, Self>, D::Error>
+                        where T: serde::de::DeserializeOwned,
+                              D: Deserializer
+                    {
+                        deserializer.deserialize_value(bytes)
+                    }
                 }
             }
-        }
-    } else {
-        quote! {}
-    };
-    [
-        quote! {
-            #[doc = #doc]
-            pub struct #def;
-            impl InternalTelemetryDefinition for #def {
-                type TMValueType = #tmty;
-                const ID: u16 = #tm_id;
+        } else {
+            quote! {}
+        };
+        let serializer_func = if cfg!(feature = "ground") {
+            quote! {
+                impl SerializableTMValue<#def> for #tmty {
+                    fn serialize_ground<T, S>(&self, _def: &#def, timestamp: T, serializer: &S)
+                        -> Result<alloc::vec::Vec<(&'static str, alloc::vec::Vec<u8>)>, S::Error>
+                        where T: serde::Serialize + Clone + Copy,
+                              S: Serializer
+                    {
+                        let mut serialized_pairs = alloc::vec::Vec::new();
+                        #({
+                            let nats_value = GroundTelemetry::new(timestamp, (#funcs)(self));
+                            let bytes = serializer.serialize_value(&nats_value)?;
+                            serialized_pairs.push((concat!(#address, ".", stringify!(#address_endings)), bytes));
+                        })*
+
+                        let raw_nats_value = GroundTelemetry::new(timestamp, self);
+                        let raw_bytes = serializer.serialize_value(&raw_nats_value)?;
+                        serialized_pairs.push((#address, raw_bytes));
+
+                        Ok(serialized_pairs)
+                    }
+                }
+                #deserializer_impl
             }
-            impl const TelemetryDefinition for #def {
-                fn id(&self) -> u16 { Self::ID }
-                fn address(&self) -> &str { #address }
+        } else {
+            quote! {}
+        };
+        let calibrations_const = if cfg!(feature = "ground") {
+            quote! {
+                const CALIBRATIONS: &'static [(&'static str, &'static str)] =
+                    &[#((stringify!(#address_endings), stringify!(#funcs))),*];
             }
-            #serializer_func
-        },
-        quote! {
-            #tm_id => Ok(&#def_addr),
-        },
-        quote! {
-            #address => Ok(&#def_addr),
-        },
-        quote! {
-            #def::MAX_BYTE_SIZE,
-        },
-    ]
-}
-
-fn generate_module_recursive(
-    address: &Vec<syn::Ident>,
-    id: &mut u16,
-    v: &syn::ItemMod,
-) -> [TokenStream; 4] {
-    // Parse "tmm" attribute
-    if let Some(module_id) = v
-        .attrs
-        .iter()
-        .find(|attr| attr.path().is_ident(TM_MODULE_MACRO_NAME))
-        .map(|v| {
-            v.parse_args_with(Punctuated::<Meta, Token![,]>::parse_separated_nonempty)
-                .expect(&format!(
-                    "Could not parse {} attribute parameters",
-                    TM_MODULE_MACRO_NAME
-                ))
-                .iter()
-                .filter_map(|m| m.require_name_value().ok())
-                .filter(|m| m.path.get_ident().filter(|p| *p == "id").is_some())
-                .map(|m| {
-                    if let syn::Expr::Lit(value) = &m.value {
-                        value
-                    } else {
-                        panic!("unexpected attribute value type")
-                    }
-                })
-                .map(|m| {
-                    if let syn::Lit::Int(value) = &m.lit {
-                        value
-                    } else {
-                        panic!("unexpected attribute value type")
-                    }
-                })
-                .next()
-                .map(|lit| lit.base10_parse().unwrap())
-        })
-        .flatten()
-    {
-        if *id > module_id {
-            panic!("like schedules, ids should only move in one direction");
-        }
-        *id = module_id;
+        } else {
+            quote! {}
+        };
+        (
+            quote! {
+                #[doc = #doc]
+                pub struct #def;
+                impl InternalTelemetryDefinition for #def {
+                    type TMValueType = #tmty;
+                    const ID: u16 = #tm_id;
+                    const ADDRESS: &'static str = #address;
+                    #calibrations_const
+                }
+                impl const TelemetryDefinition for #def {
+                    fn id(&self) -> u16 { Self::ID }
+                    fn address(&self) -> &str { #address }
+                }
+                #serializer_func
+            },
+            [
+                quote! {
+                    definition_schema::<#def_addr>(),
+                },
+                quote! {
+                    <#def_addr as InternalTelemetryDefinition>::HASH,
+                },
+                quote! {
+                    #def,
+                },
+            ],
+        )
     }
-    let start_id = *id;
-
-    let module_name = v.ident.clone();
-    let mut address = address.clone();
-    address.push(module_name.clone());
-    let [module_content, id_getters, address_getters, byte_lengths] = generate_tree(
-        address,
-        id,
-        &v.content.as_ref().expect("module sould not be empty").1,
-    );
 
-    [
-        quote! {
-            pub mod #module_name {
-                use super::*;
-                pub const fn id_range() -> (u16, u16) {
-                    (#start_id, #id)
-                }
-                pub const MAX_BYTE_SIZE: usize = {
-                    let SIZES = [#byte_lengths];
-                    let mut max = 0;
-                    let mut i = 0;
-                    while i < SIZES.len() {
-                        if SIZES[i] > max {
-                            max = SIZES[i];
-                        }
-                        i += 1;
-                    }
-                    max
-                };
-                #module_content
-            }
-        },
-        id_getters,
-        address_getters,
-        quote! {
-            #module_name::MAX_BYTE_SIZE,
-        },
-    ]
+    fn module(&self, module: &Ident, streams: [TokenStream; 3]) -> (TokenStream, [TokenStream; 3]) {
+        let [definition_schemas, definition_hashes, definitions] = streams;
+        let definitions = definition_paths(definitions);
+        let parent_definitions = definitions.iter().map(|path| quote! { #module::#path, });
+        (
+            quote! {
+                /// Every definition in this module and its submodules, in id order
+                pub const DEFINITIONS: &[&'static dyn TelemetryDefinition] = &[#(&#definitions),*];
+            },
+            [
+                definition_schemas,
+                definition_hashes,
+                parent_definitions.collect(),
+            ],
+        )
+    }
 }
 
-fn generate_tree(address: Vec<syn::Ident>, id: &mut u16, items: &Vec<Item>) -> [TokenStream; 4] {
-    items
-        .iter()
-        .map(|v| match v {
-            syn::Item::Struct(v) => generate_struct(&address, id, v),
-            syn::Item::Mod(v) => generate_module_recursive(&address, id, v),
-            _ => panic!("module should only contain other modules and structs"),
-        })
-        .fold(from_fn(|_| TokenStream::new()), |acc, src| {
-            zip(acc, src)
-                .map(|(mut acc, src)| {
-                    acc.extend(src);
-                    acc
-                })
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap()
-        })
+/// Paths of the definitions of a subtree, relative to the module they were collected in
+fn definition_paths(definitions: TokenStream) -> Vec<syn::Path> {
+    Punctuated::<syn::Path, Token![,]>::parse_terminated
+        .parse2(definitions)
+        .expect("definition paths should parse")
+        .into_iter()
+        .collect()
 }
 
 pub fn impl_macro(ast: syn::Item, mut id: u16, tmtc_system_address: syn::Path) -> TokenStream {
@@ -270,8 +176,23 @@ pub fn impl_macro(ast: syn::Item, mut id: u16, tmtc_system_address: syn::Path) -
     let start_id = id;
     let id_ref = &mut id;
 
-    let [module_content, id_getters, address_getters, byte_lengths] =
-        generate_tree(vec![root_mod_ident.clone()], id_ref, &root_mod_content.1);
+    let tree =
+        TelemetryTree.generate_tree(vec![root_mod_ident.clone()], id_ref, &root_mod_content.1);
+    let max_byte_size = max_byte_size(&tree.byte_lengths);
+    let (module_content, id_getters, address_getters) =
+        (tree.content, tree.id_getters, tree.address_getters);
+    let [definition_schemas, definition_hashes, definitions] = tree.streams;
+    let definitions = definition_paths(definitions);
+    let schema_func = if cfg!(feature = "ground") {
+        quote! {
+            /// Layout of every definition in this tree, for decoding without the rust types
+            pub fn schema() -> TelemetrySchema {
+                TelemetrySchema::new([#definition_schemas])
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     quote! {
         pub mod #root_mod_ident {
@@ -291,18 +212,13 @@ pub fn impl_macro(ast: syn::Item, mut id: u16, tmtc_system_address: syn::Path) -
             pub const fn id_range() -> (u16, u16) {
                 (#start_id, #id_ref)
             }
-            pub const MAX_BYTE_SIZE: usize = {
-                let SIZES = [#byte_lengths];
-                let mut max = 0;
-                let mut i = 0;
-                while i < SIZES.len() {
-                    if SIZES[i] > max {
-                        max = SIZES[i];
-                    }
-                    i += 1;
-                }
-                max
-            };
+            pub const MAX_BYTE_SIZE: usize = #max_byte_size;
+            /// Every definition in this tree, in id order
+            pub const DEFINITIONS: &[&'static dyn TelemetryDefinition] = &[#(&#definitions),*];
+            /// Hash over the addresses, ids and value layouts of this tree.
+            /// Flight and ground builds only agree on the dictionary if their hashes match
+            pub const DICTIONARY_HASH: u64 = layout_hash("Dictionary", &[#definition_hashes]);
+            #schema_func
             #module_content
         }
     }
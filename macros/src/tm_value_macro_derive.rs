@@ -1,157 +1,753 @@
+use std::iter::zip;
+
 use crate::macro_utils::parse_type_path;
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Ident;
-
-fn impl_struct(type_name: syn::Ident, tm_value_struct: syn::DataStruct) -> TokenStream {
-    let struct_type_parsers = tm_value_struct.fields.iter().map(|f| {
-        let ident = &f.ident;
-        let ty = parse_type_path(&f.ty);
-        quote! {
-            #ident: {
-                let (len, value) = #ty::read(&bytes[pos..])?;
-                pos += len;
-                value
+use proc_macro2::{Literal, TokenStream};
+use quote::{ToTokens, quote};
+use syn::{Attribute, Ident, Meta, Token, parse_quote, punctuated::Punctuated};
+
+const TM_ATTRIBUTE_NAME: &str = "tm";
+
+/// One `name` or `name = value` parameter of a `#[tm(...)]` attribute
+struct TmArg {
+    name: String,
+    value: Option<syn::Expr>,
+    span: proc_macro2::Span,
+}
+
+/// Parameters of all `#[tm(...)]` attributes. Parsed as nested meta
+/// since `Meta` does not accept keywords like `const` as names
+fn parse_tm_args(attrs: &[Attribute]) -> Vec<TmArg> {
+    let mut args = Vec::new();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(TM_ATTRIBUTE_NAME))
+    {
+        attr.parse_nested_meta(|meta| {
+            let ident = meta
+                .path
+                .get_ident()
+                .ok_or(meta.error("expected a single name"))?;
+            let (name, span) = (ident.to_string(), ident.span());
+            let value = if meta.input.peek(Token![=]) {
+                Some(meta.value()?.parse()?)
+            } else {
+                None
+            };
+            args.push(TmArg { name, value, span });
+            Ok(())
+        })
+        .expect(&format!(
+            "Could not parse {} attribute parameters",
+            TM_ATTRIBUTE_NAME
+        ));
+    }
+    args
+}
+
+fn find_tm_arg<'a>(args: &'a [TmArg], name: &str) -> Option<&'a TmArg> {
+    args.iter().find(|arg| arg.name == name)
+}
+
+/// `#[tm(...)]` parameters of types and of fields
+const TYPE_TM_ARGS: [&str; 3] = ["packed", "bit_order", "endian"];
+const FIELD_TM_ARGS: [&str; 4] = ["skip", "const", "bits", "endian"];
+
+/// Rejects unknown `#[tm(...)]` parameters and packing parameters of byte aligned types,
+/// a typo would otherwise silently change the layout
+fn check_tm_args(ast: &syn::DeriveInput) -> syn::Result<()> {
+    let check = |args: &[TmArg], known: &[&str], kind: &str| match args
+        .iter()
+        .find(|arg| !known.contains(&arg.name.as_str()))
+    {
+        Some(arg) => Err(syn::Error::new(
+            arg.span,
+            format!("unknown {} parameter `{}`", kind, arg.name),
+        )),
+        None => Ok(()),
+    };
+    let packed_only = |arg: Option<&TmArg>| match arg {
+        Some(arg) => Err(syn::Error::new(
+            arg.span,
+            format!("`{}` is only supported in #[tm(packed)] structs", arg.name),
+        )),
+        None => Ok(()),
+    };
+    let type_args = parse_tm_args(&ast.attrs);
+    check(&type_args, &TYPE_TM_ARGS, "type")?;
+    let packed = find_tm_arg(&type_args, "packed").is_some();
+    if !packed {
+        packed_only(find_tm_arg(&type_args, "bit_order"))?;
+    }
+    let fields: Vec<&syn::Field> = match &ast.data {
+        syn::Data::Struct(tm_value_struct) => tm_value_struct.fields.iter().collect(),
+        syn::Data::Enum(tm_value_enum) => {
+            for variant in &tm_value_enum.variants {
+                check(&parse_tm_args(&variant.attrs), &[], "variant")?;
             }
+            tm_value_enum
+                .variants
+                .iter()
+                .flat_map(|v| v.fields.iter())
+                .collect()
         }
-    });
-    let struct_byte_parsers = tm_value_struct.fields.iter().map(|f| {
-        let ident = &f.ident;
+        syn::Data::Union(_) => Vec::new(),
+    };
+    for field in fields {
+        let field_args = parse_tm_args(&field.attrs);
+        check(&field_args, &FIELD_TM_ARGS, "field")?;
+        if !packed {
+            packed_only(find_tm_arg(&field_args, "bits"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Bit order of a `#[tm(packed)]` type, none if it is byte aligned
+fn parse_packed(attrs: &[Attribute]) -> Option<TokenStream> {
+    let args = parse_tm_args(attrs);
+    find_tm_arg(&args, "packed")?;
+    let order = find_tm_arg(&args, "bit_order")
+        .map(|arg| {
+            let Some(syn::Expr::Path(order)) = &arg.value else {
+                panic!("bit_order should be msb or lsb");
+            };
+            match order.path.get_ident().map(|i| i.to_string()).as_deref() {
+                Some("msb") => quote! { Msb },
+                Some("lsb") => quote! { Lsb },
+                _ => panic!("bit_order should be msb or lsb"),
+            }
+        })
+        .unwrap_or(quote! { Msb });
+    Some(order)
+}
+
+/// Bit width of a field given by `#[tm(bits = N)]`
+fn parse_bits(attrs: &[Attribute]) -> Option<u32> {
+    find_tm_arg(&parse_tm_args(attrs), "bits").map(|arg| {
+        let Some(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(bits),
+            ..
+        })) = &arg.value
+        else {
+            panic!("bits should be an integer");
+        };
+        let bits = bits.base10_parse().expect("bits should be an u32");
+        if bits == 0 || bits > 64 {
+            panic!("bits should be between 1 and 64");
+        }
+        bits
+    })
+}
+
+/// Byte order given by `#[tm(endian = "big")]`, true for big endian
+fn parse_endian(attrs: &[Attribute]) -> Option<bool> {
+    find_tm_arg(&parse_tm_args(attrs), "endian").map(|arg| {
+        let endian = match &arg.value {
+            Some(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(endian),
+                ..
+            })) => endian.value(),
+            Some(syn::Expr::Path(endian)) => endian
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+        match endian.as_str() {
+            "big" => true,
+            "little" => false,
+            _ => panic!("endian should be big or little"),
+        }
+    })
+}
+
+/// `read`/`write` or their big endian counterparts
+fn endian_methods(big_endian: bool) -> (TokenStream, TokenStream) {
+    if big_endian {
+        (quote! { read_be }, quote! { write_be })
+    } else {
+        (quote! { read }, quote! { write })
+    }
+}
+
+/// Byte order of a schema, `None` if it follows the surrounding type
+fn endian_tokens(big_endian: Option<bool>) -> TokenStream {
+    match big_endian {
+        Some(big_endian) => quote! { Some(#big_endian) },
+        None => quote! { None },
+    }
+}
+
+/// Byte order as part of a layout hash
+fn endian_hash(big_endian: Option<bool>) -> u64 {
+    match big_endian {
+        Some(false) => 0,
+        Some(true) => 1,
+        None => 2,
+    }
+}
+
+/// How a field is put on the wire
+enum FieldMode {
+    Wire,
+    /// `#[tm(skip)]`, not sent and filled from `Default` on read
+    Skip,
+    /// `#[tm(const = ..)]`, always sent as the given value and checked on read
+    Const(syn::Expr),
+}
+
+fn parse_field_mode(attrs: &[Attribute]) -> FieldMode {
+    let args = parse_tm_args(attrs);
+    let skip = find_tm_arg(&args, "skip").is_some();
+    let constant = find_tm_arg(&args, "const")
+        .map(|arg| arg.value.clone().expect("const should be given a value"));
+    match (skip, constant) {
+        (false, None) => FieldMode::Wire,
+        (true, None) => FieldMode::Skip,
+        (false, Some(value)) => FieldMode::Const(value),
+        (true, Some(_)) => panic!("a field can not be both skipped and constant"),
+    }
+}
+
+/// Expression reading the field at `pos` of `bytes`
+fn field_reader(field: &syn::Field, big_endian: bool) -> TokenStream {
+    let ty = parse_type_path(&field.ty);
+    let (read, _) = endian_methods(parse_endian(&field.attrs).unwrap_or(big_endian));
+    let read = quote! {
+        let (len, value) = #ty::#read(bytes.get(pos..).ok_or(TMValueError::OutOfMemory)?)?;
+        pos += len;
+    };
+    match parse_field_mode(&field.attrs) {
+        FieldMode::Wire => quote! {{
+            #read
+            value
+        }},
+        FieldMode::Skip => quote! { Default::default() },
+        FieldMode::Const(constant) => quote! {{
+            #read
+            if value != #constant {
+                return Err(TMValueError::BadConstant);
+            }
+            value
+        }},
+    }
+}
+
+/// Statements writing the field `value` at `pos` of `mem`
+fn field_writer(field: &syn::Field, value: TokenStream, big_endian: bool) -> TokenStream {
+    let ty = &field.ty;
+    let (_, write) = endian_methods(parse_endian(&field.attrs).unwrap_or(big_endian));
+    match parse_field_mode(&field.attrs) {
+        FieldMode::Wire => quote! {
+            pos += #value.#write(mem.get_mut(pos..).ok_or(TMValueError::OutOfMemory)?)?;
+        },
+        FieldMode::Skip => quote! {},
+        FieldMode::Const(constant) => quote! {
+            let constant: #ty = #constant;
+            pos += constant.#write(mem.get_mut(pos..).ok_or(TMValueError::OutOfMemory)?)?;
+        },
+    }
+}
+
+fn field_size(field: &syn::Field) -> TokenStream {
+    let ty = &field.ty;
+    match parse_field_mode(&field.attrs) {
+        FieldMode::Skip => quote! { 0 },
+        FieldMode::Wire | FieldMode::Const(_) => quote! { <#ty as TMValue>::MAX_BYTE_SIZE },
+    }
+}
+
+/// `TMValue::schema` returning `schema`, only generated for ground builds
+fn schema_fn(schema: TokenStream) -> TokenStream {
+    if cfg!(feature = "ground") {
         quote! {
-            pos += self.#ident.write(&mut mem[pos..])?;
+            fn schema() -> tmtc_system::schema::TypeSchema {
+                #schema
+            }
         }
+    } else {
+        quote! {}
+    }
+}
+
+/// Schemas of the fields on the wire, in wire order
+fn field_schemas(fields: &syn::Fields, big_endian: Option<bool>) -> Vec<TokenStream> {
+    zip(fields.members(), fields)
+        .filter(|(_, f)| !matches!(parse_field_mode(&f.attrs), FieldMode::Skip))
+        .map(|(member, f)| {
+            let name = member.to_token_stream().to_string();
+            let ty = &f.ty;
+            let big_endian = endian_tokens(parse_endian(&f.attrs).or(big_endian));
+            quote! {
+                tmtc_system::schema::FieldSchema::new(
+                    #name,
+                    #big_endian,
+                    <#ty as TMValue>::schema(),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Layout hashes of the fields on the wire, in wire order
+fn field_hashes(fields: &syn::Fields, big_endian: Option<bool>) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter_map(|f| {
+            let kind = match parse_field_mode(&f.attrs) {
+                FieldMode::Wire => "Field",
+                FieldMode::Skip => return None,
+                FieldMode::Const(_) => "ConstField",
+            };
+            let ty = &f.ty;
+            let big_endian = endian_hash(parse_endian(&f.attrs).or(big_endian));
+            Some(quote! {
+                tmtc_system::_internal::layout_hash(
+                    #kind,
+                    &[<#ty as TMValue>::LAYOUT_HASH, #big_endian],
+                )
+            })
+        })
+        .collect()
+}
+
+fn impl_packed_struct(
+    impl_header: TokenStream,
+    name: &Ident,
+    tm_value_struct: syn::DataStruct,
+    order: TokenStream,
+) -> TokenStream {
+    if tm_value_struct
+        .fields
+        .iter()
+        .any(|f| !matches!(parse_field_mode(&f.attrs), FieldMode::Wire))
+    {
+        panic!("skipped and constant fields are not supported in #[tm(packed)] structs");
+    }
+    let internal = quote! { tmtc_system::_internal };
+    let members: Vec<_> = tm_value_struct.fields.members().collect();
+    let types: Vec<_> = tm_value_struct.fields.iter().map(|f| &f.ty).collect();
+    let bits: Vec<_> = tm_value_struct
+        .fields
+        .iter()
+        .map(|f| match parse_bits(&f.attrs) {
+            Some(bits) => Literal::u32_unsuffixed(bits).into_token_stream(),
+            None => {
+                let ty = &f.ty;
+                quote! { <#ty as #internal::BitPacked>::BITS }
+            }
+        })
+        .collect();
+    let names = members.iter().map(|m| m.to_token_stream().to_string());
+    let schema_fn = schema_fn(quote! {
+        tmtc_system::schema::TypeSchema::packed(
+            stringify!(#name),
+            #internal::BitOrder::#order,
+            [#(tmtc_system::schema::PackedFieldSchema::new(
+                #names,
+                #bits,
+                <#types as #internal::BitPacked>::PRIMITIVE,
+            )),*],
+        )
     });
-    let struct_types = tm_value_struct.fields.iter().map(|f| &f.ty);
+    let order_hash = order.to_string() == "Lsb";
     quote! {
-        impl TMValue for #type_name {
-            const MAX_BYTE_SIZE: usize = #(<#struct_types as TMValue>::MAX_BYTE_SIZE)+*;
+        #impl_header {
+            #schema_fn
+            const LAYOUT_HASH: u64 = #internal::layout_hash(
+                "Packed",
+                &[
+                    #order_hash as u64,
+                    #(#internal::layout_hash(
+                        "Bits",
+                        &[<#types as #internal::BitPacked>::LAYOUT_HASH, (#bits) as u64],
+                    )),*
+                ],
+            );
+            const MAX_BYTE_SIZE: usize = {
+                use #internal::BitPacked;
+                #(assert!(#bits <= <#types as BitPacked>::BITS, "field is narrower than its bit width");)*
+                let bits: u32 = 0 #(+ #bits)*;
+                bits.div_ceil(8) as usize
+            };
             fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
-                let mut pos = 0;
+                use #internal::{BitOrder, BitPacked, BitReader};
+                let bytes = bytes.get(..Self::MAX_BYTE_SIZE).ok_or(TMValueError::OutOfMemory)?;
+                let mut reader = BitReader::new(bytes, BitOrder::#order);
                 let value = Self {
-                    #(#struct_type_parsers),*
+                    #(#members: <#types as BitPacked>::from_bits(reader.read(#bits), #bits)?),*
                 };
-                Ok((pos, value))
+                reader.finish()?;
+                Ok((Self::MAX_BYTE_SIZE, value))
             }
             fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
-                let mut pos = 0;
-                #(#struct_byte_parsers)*
-                Ok(pos)
+                use #internal::{BitOrder, BitPacked, BitWriter};
+                let mem = mem.get_mut(..Self::MAX_BYTE_SIZE).ok_or(TMValueError::OutOfMemory)?;
+                let mut writer = BitWriter::new(mem, BitOrder::#order);
+                #(writer.write(BitPacked::to_bits(&self.#members, #bits)?, #bits);)*
+                Ok(Self::MAX_BYTE_SIZE)
+            }
+            // the bit order fixes the layout, it is the same in either byte order
+            fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+                Self::read(bytes)
+            }
+            fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+                self.write(mem)
             }
         }
     }
 }
 
-fn impl_enum(type_name: syn::Ident, tm_value_enum: syn::DataEnum) -> TokenStream {
+fn impl_struct(
+    impl_header: TokenStream,
+    name: &Ident,
+    tm_value_struct: syn::DataStruct,
+    attrs: &[Attribute],
+) -> TokenStream {
+    let big_endian = parse_endian(attrs);
+    // fields without a byte order of their own follow the struct, then the surrounding type
+    let (read, write) = struct_body(&tm_value_struct.fields, big_endian.unwrap_or(false));
+    let (read_be, write_be) = struct_body(&tm_value_struct.fields, big_endian.unwrap_or(true));
+    let field_sizes = tm_value_struct.fields.iter().map(field_size);
+    let field_schemas = field_schemas(&tm_value_struct.fields, big_endian);
+    let schema_fn = schema_fn(quote! {
+        tmtc_system::schema::TypeSchema::structure(stringify!(#name), [#(#field_schemas),*])
+    });
+    let field_hashes = field_hashes(&tm_value_struct.fields, big_endian);
+    quote! {
+        #impl_header {
+            #schema_fn
+            const LAYOUT_HASH: u64 =
+                tmtc_system::_internal::layout_hash("Struct", &[#(#field_hashes),*]);
+            const MAX_BYTE_SIZE: usize = 0 #(+ #field_sizes)*;
+            fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+                #read
+            }
+            fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+                #write
+            }
+            fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+                #read_be
+            }
+            fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+                #write_be
+            }
+        }
+    }
+}
+
+/// Bodies of `read` and `write` of a struct, `big_endian` is the default of the fields
+fn struct_body(fields: &syn::Fields, big_endian: bool) -> (TokenStream, TokenStream) {
+    // `Self { 0: .. }` also constructs tuple structs, `Self {}` unit structs
+    let members = fields.members();
+    let field_readers = fields.iter().map(|f| field_reader(f, big_endian));
+    let field_writers = zip(fields.members(), fields)
+        .map(|(member, f)| field_writer(f, quote! { self.#member }, big_endian));
+    (
+        quote! {
+            let mut pos = 0;
+            let value = Self {
+                #(#members: #field_readers),*
+            };
+            Ok((pos, value))
+        },
+        quote! {
+            let mut pos = 0;
+            #(#field_writers)*
+            Ok(pos)
+        },
+    )
+}
+
+/// Integer type of the enum tag given by `#[repr(..)]`, `u8` by default
+fn parse_repr(attrs: &[Attribute]) -> syn::Result<Ident> {
+    let repr = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .flat_map(|attr| {
+            attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .expect("Could not parse repr attribute parameters")
+        })
+        .filter_map(|m| m.path().get_ident().cloned())
+        .find(|ident| {
+            INTEGER_TAGS.iter().any(|(name, _, _)| ident == name)
+                || UNSUPPORTED_TAGS.iter().any(|name| ident == name)
+        });
+    match repr {
+        Some(repr) if UNSUPPORTED_TAGS.iter().any(|name| repr == name) => {
+            Err(syn::Error::new_spanned(
+                &repr,
+                format!(
+                    "#[repr({})] is not supported as TMValue tag, use one of u8, u16, u32, u64, i8, i16, i32 or i64",
+                    repr
+                ),
+            ))
+        }
+        Some(repr) => Ok(repr),
+        None => Ok(Ident::new("u8", proc_macro2::Span::call_site())),
+    }
+}
+
+/// Integer reprs without a fixed size or wider than the tag read and write support
+const UNSUPPORTED_TAGS: [&str; 4] = ["usize", "isize", "u128", "i128"];
+
+/// Supported tag types with their value range
+const INTEGER_TAGS: [(&str, i128, i128); 8] = [
+    ("u8", 0, u8::MAX as i128),
+    ("u16", 0, u16::MAX as i128),
+    ("u32", 0, u32::MAX as i128),
+    ("u64", 0, u64::MAX as i128),
+    ("i8", i8::MIN as i128, i8::MAX as i128),
+    ("i16", i16::MIN as i128, i16::MAX as i128),
+    ("i32", i32::MIN as i128, i32::MAX as i128),
+    ("i64", i64::MIN as i128, i64::MAX as i128),
+];
+
+fn parse_discriminant(expr: &syn::Expr) -> i128 {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(value),
+            ..
+        }) => value
+            .base10_parse()
+            .expect("discriminant should be an integer"),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => -parse_discriminant(expr),
+        syn::Expr::Group(group) => parse_discriminant(&group.expr),
+        _ => panic!("discriminants should be integer literals"),
+    }
+}
+
+/// Tag of every variant, explicit discriminants are kept and the others count up like in rust
+fn enum_tags(tm_value_enum: &syn::DataEnum, repr: &Ident) -> Vec<TokenStream> {
+    let (_, min, max) = INTEGER_TAGS
+        .iter()
+        .find(|(name, _, _)| repr == name)
+        .unwrap();
+    let mut next = 0;
+    let mut tags = Vec::new();
+    for v in &tm_value_enum.variants {
+        let tag = v
+            .discriminant
+            .as_ref()
+            .map(|(_, expr)| parse_discriminant(expr))
+            .unwrap_or(next);
+        if tag < *min || tag > *max {
+            panic!(
+                "tag {} of variant {} does not fit into {}, use a wider #[repr(..)]",
+                tag, v.ident, repr
+            );
+        }
+        if tags.contains(&tag) {
+            panic!("duplicate tag {} of variant {}", tag, v.ident);
+        }
+        tags.push(tag);
+        next = tag + 1;
+    }
+    tags.iter()
+        .map(|tag| {
+            let value = Literal::u128_unsuffixed(tag.unsigned_abs());
+            if *tag < 0 {
+                quote! { -#value }
+            } else {
+                quote! { #value }
+            }
+        })
+        .collect()
+}
+
+fn impl_enum(
+    impl_header: TokenStream,
+    name: &Ident,
+    tm_value_enum: syn::DataEnum,
+    attrs: &[Attribute],
+) -> TokenStream {
+    let repr = match parse_repr(attrs) {
+        Ok(repr) => repr,
+        Err(error) => return error.to_compile_error(),
+    };
+    let tags = enum_tags(&tm_value_enum, &repr);
+    let big_endian = parse_endian(attrs);
+    // the tag and fields without a byte order of their own follow the enum,
+    // then the surrounding type
+    let (read, write) = enum_body(&tm_value_enum, &repr, &tags, big_endian.unwrap_or(false));
+    let (read_be, write_be) = enum_body(&tm_value_enum, &repr, &tags, big_endian.unwrap_or(true));
+    // like structs, `Self::Variant { 0: .. }` covers tuple and unit variants
     let enum_variant_size_cmp = tm_value_enum.variants.iter().map(|v| {
-        let iter: Box<dyn Iterator<Item = _>> = match &v.fields {
-            syn::Fields::Unit => Box::new(std::iter::empty()),
-            syn::Fields::Unnamed(unnamed_fields) => Box::new(unnamed_fields.unnamed.iter()),
-            syn::Fields::Named(named_fields) => Box::new(named_fields.named.iter()),
-        };
-        let sizes = iter
-            .map(|f| parse_type_path(&f.ty))
-            .map(|ty| quote! { #ty::MAX_BYTE_SIZE });
+        let sizes = v.fields.iter().map(field_size);
         quote! {
-            let variant_size = 1usize #(+ #sizes)*;
+            let variant_size = <#repr as TMValue>::MAX_BYTE_SIZE #(+ #sizes)*;
             if variant_size > m {
                 m = variant_size;
             }
         }
     });
-    let enum_variant_parsers = tm_value_enum.variants.iter().enumerate().map(|(i, v)| {
-        let index = i as u8;
-        let ident = &v.ident;
-        match &v.fields {
-            syn::Fields::Unit => {
-                quote! {
-                    #index => {
-                        Self::#ident
-                    }
-                }
-            }
-            syn::Fields::Unnamed(unnamed_fields) => {
-                let field_parsers = unnamed_fields.unnamed.iter().map(|v| {
-                    let ty = parse_type_path(&v.ty);
-                    quote! {{
-                        let (len, value) = #ty::read(&bytes[pos..])?;
-                        pos += len;
-                        value
-                    }}
-                });
-                quote! {
-                    #index => {
-                        Self::#ident(#(#field_parsers),*)
-                    }
-                }
-            }
-            syn::Fields::Named(_) => {
-                unimplemented!("enums with named fields are currently not supported as TMValue")
-            }
+    let variant_schemas = zip(&tm_value_enum.variants, &tags).map(|(v, tag)| {
+        let variant = v.ident.to_string();
+        let field_schemas = field_schemas(&v.fields, big_endian);
+        quote! {
+            tmtc_system::schema::VariantSchema::new(#variant, #tag, [#(#field_schemas),*])
         }
     });
-    let enum_byte_parsers = tm_value_enum.variants.iter().enumerate().map(|(i, v)| {
-        let ident = &v.ident;
-        let index = i as u8;
-        match &v.fields {
-            syn::Fields::Unit => {
-                quote! {
-                    Self::#ident => {
-                        mem[0] = #index;
-                    }
-                }
-            }
-            syn::Fields::Unnamed(unnamed_fields) => {
-                let field_idents = (0..unnamed_fields.unnamed.len())
-                    .map(|i| Ident::new(&format!("v{}", i), proc_macro2::Span::call_site()));
-                let field_parsers = field_idents.clone().map(|ident| {
-                    quote! {
-                        pos += #ident.write(&mut mem[pos..])?;
-                    }
-                });
-                quote! {
-                    Self::#ident(#(#field_idents),*) => {
-                        mem[0] = #index;
-                        #(#field_parsers)*
-                    }
-                }
-            }
-            syn::Fields::Named(_) => {
-                unimplemented!("enums with named fields are currently not supported as TMValue")
-            }
+    let tag_repr = repr.to_string();
+    let (tag_kind, tag_bits) = tag_repr.split_at(1);
+    let tag_kind = if tag_kind == "u" {
+        quote! { Unsigned }
+    } else {
+        quote! { Signed }
+    };
+    let tag_bytes = tag_bits.parse::<u8>().unwrap() / 8;
+    let tag_endian = endian_tokens(big_endian);
+    let schema_fn = schema_fn(quote! {
+        tmtc_system::schema::TypeSchema::enumeration(
+            stringify!(#name),
+            tmtc_system::schema::Primitive::#tag_kind { bytes: #tag_bytes },
+            #tag_endian,
+            [#(#variant_schemas),*],
+        )
+    });
+    let tag_hash = endian_hash(big_endian);
+    let variant_hashes = zip(&tm_value_enum.variants, &tags).map(|(v, tag)| {
+        let field_hashes = field_hashes(&v.fields, big_endian);
+        quote! {
+            tmtc_system::_internal::layout_hash(
+                "Variant",
+                &[(#tag) as i128 as u64, #(#field_hashes),*],
+            )
         }
     });
     quote! {
-        impl TMValue for #type_name {
+        #impl_header {
+            #schema_fn
+            const LAYOUT_HASH: u64 = tmtc_system::_internal::layout_hash(
+                "Enum",
+                &[
+                    <#repr as TMValue>::LAYOUT_HASH,
+                    #tag_hash,
+                    #(#variant_hashes),*
+                ],
+            );
             const MAX_BYTE_SIZE: usize = {
                 let mut m = 0;
                 #(#enum_variant_size_cmp)*
                 m
             };
             fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
-                let mut pos = 1;
-                let value = match bytes[0] {
-                    #(#enum_variant_parsers)*
-                    _ => return Err(TMValueError::BadEnumVariant)
-                };
-                Ok((pos, value))
+                #read
             }
             fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
-                let mut pos = 1;
-                match self {
-                    #(#enum_byte_parsers)*
-                }
-                Ok(pos)
+                #write
+            }
+            fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+                #read_be
+            }
+            fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+                #write_be
             }
         }
     }
 }
 
-pub fn impl_macro(ast: syn::DeriveInput) -> TokenStream {
-    let type_name = ast.ident.clone();
+/// Bodies of `read` and `write` of an enum, `big_endian` is the default of the tag and fields
+fn enum_body(
+    tm_value_enum: &syn::DataEnum,
+    repr: &Ident,
+    tags: &[TokenStream],
+    big_endian: bool,
+) -> (TokenStream, TokenStream) {
+    let (read, write) = endian_methods(big_endian);
+    let enum_variant_parsers = zip(&tm_value_enum.variants, tags).map(|(v, index)| {
+        let ident = &v.ident;
+        let members = v.fields.members();
+        let field_readers = v.fields.iter().map(|f| field_reader(f, big_endian));
+        quote! {
+            #index => {
+                Self::#ident { #(#members: #field_readers),* }
+            }
+        }
+    });
+    let enum_byte_parsers = zip(&tm_value_enum.variants, tags).map(|(v, index)| {
+        let ident = &v.ident;
+        let members = v.fields.members();
+        // bind to fresh names, fields may be called `pos` or `mem`
+        let bindings: Vec<_> = v
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| match parse_field_mode(&f.attrs) {
+                FieldMode::Wire => Ident::new(&format!("v{}", i), proc_macro2::Span::call_site())
+                    .into_token_stream(),
+                FieldMode::Skip | FieldMode::Const(_) => quote! { _ },
+            })
+            .collect();
+        let field_writers = zip(&v.fields, &bindings)
+            .map(|(f, binding)| field_writer(f, binding.clone(), big_endian));
+        quote! {
+            Self::#ident { #(#members: #bindings),* } => {
+                pos += <#repr as TMValue>::#write(&(#index), mem)?;
+                #(#field_writers)*
+            }
+        }
+    });
+    (
+        quote! {
+            let (mut pos, tag) = <#repr as TMValue>::#read(bytes)?;
+            let value = match tag {
+                #(#enum_variant_parsers)*
+                _ => return Err(TMValueError::BadEnumVariant)
+            };
+            Ok((pos, value))
+        },
+        quote! {
+            let mut pos = 0;
+            match self {
+                #(#enum_byte_parsers)*
+            }
+            Ok(pos)
+        },
+    )
+}
 
+/// `impl TMValue for` header bounding every type parameter by `bound`
+fn impl_header(ast: &syn::DeriveInput, bound: TokenStream) -> TokenStream {
+    let type_name = &ast.ident;
+    let mut generics = ast.generics.clone();
+    let params: Vec<_> = generics.type_params().map(|p| p.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote! { #param: #bound });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics TMValue for #type_name #ty_generics #where_clause
+    }
+}
+
+pub fn impl_macro(ast: syn::DeriveInput) -> TokenStream {
+    if let Err(error) = check_tm_args(&ast) {
+        return error.to_compile_error();
+    }
+    if let Some(order) = parse_packed(&ast.attrs) {
+        let impl_header = impl_header(&ast, quote! { tmtc_system::_internal::BitPacked });
+        let syn::Data::Struct(tm_value_struct) = ast.data else {
+            panic!("only structs can be packed");
+        };
+        return impl_packed_struct(impl_header, &ast.ident, tm_value_struct, order);
+    }
+    let impl_header = impl_header(&ast, quote! { TMValue });
     match ast.data {
-        syn::Data::Struct(tm_value_struct) => impl_struct(type_name, tm_value_struct),
-        syn::Data::Enum(tm_value_enum) => impl_enum(type_name, tm_value_enum),
+        syn::Data::Struct(tm_value_struct) => {
+            impl_struct(impl_header, &ast.ident, tm_value_struct, &ast.attrs)
+        }
+        syn::Data::Enum(tm_value_enum) => {
+            impl_enum(impl_header, &ast.ident, tm_value_enum, &ast.attrs)
+        }
         syn::Data::Union(_) => unimplemented!("unions are not supported as tmvalues"),
     }
 }
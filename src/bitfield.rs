@@ -1,6 +1,14 @@
+use crate::TMValueError;
+use crate::layout_hash::layout_hash;
+
 pub struct Bitfield<const N: usize> {
     storage: [u8; N],
 }
+impl<const N: usize> Default for Bitfield<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl<const N: usize> Bitfield<N> {
     pub fn new() -> Self {
         Self { storage: [0u8; N] }
@@ -22,3 +30,165 @@ impl<const N: usize> Bitfield<N> {
         (self.storage[byte] >> bit) & 1 == 1
     }
 }
+
+/// Order in which packed values fill the bytes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitOrder {
+    /// Starting at the most significant bit of the first byte, values most significant bit first
+    Msb,
+    /// Starting at the least significant bit of the first byte, values least significant bit first
+    Lsb,
+}
+impl BitOrder {
+    fn mask(&self, pos: usize) -> u8 {
+        match self {
+            Self::Msb => 0x80 >> (pos % 8),
+            Self::Lsb => 1 << (pos % 8),
+        }
+    }
+    fn bit_of_value(&self, index: u32, bits: u32) -> u32 {
+        match self {
+            Self::Msb => bits - 1 - index,
+            Self::Lsb => index,
+        }
+    }
+}
+
+/// Values that can be stored in a few bits of a packed [`crate::TMValue`]
+pub trait BitPacked: Sized {
+    const BITS: u32;
+    /// Same as the [`crate::TMValue::LAYOUT_HASH`] of the type
+    const LAYOUT_HASH: u64;
+    #[cfg(feature = "ground")]
+    const PRIMITIVE: crate::schema::Primitive;
+    /// Raw bits of the value, fails if it does not fit into `bits`
+    fn to_bits(&self, bits: u32) -> Result<u64, TMValueError>;
+    fn from_bits(raw: u64, bits: u32) -> Result<Self, TMValueError>;
+}
+impl BitPacked for bool {
+    const BITS: u32 = 1;
+    const LAYOUT_HASH: u64 = layout_hash("Bool", &[]);
+    #[cfg(feature = "ground")]
+    const PRIMITIVE: crate::schema::Primitive = crate::schema::Primitive::Bool;
+    fn to_bits(&self, _bits: u32) -> Result<u64, TMValueError> {
+        Ok(*self as u64)
+    }
+    fn from_bits(raw: u64, _bits: u32) -> Result<Self, TMValueError> {
+        match raw {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(TMValueError::BadValue),
+        }
+    }
+}
+macro_rules! unsigned_bit_packed {
+    ($type:ident) => {
+        impl BitPacked for $type {
+            const BITS: u32 = $type::BITS;
+            const LAYOUT_HASH: u64 = layout_hash("Unsigned", &[size_of::<Self>() as u64]);
+            #[cfg(feature = "ground")]
+            const PRIMITIVE: crate::schema::Primitive = crate::schema::Primitive::Unsigned {
+                bytes: size_of::<Self>() as u8,
+            };
+            fn to_bits(&self, bits: u32) -> Result<u64, TMValueError> {
+                if bits < Self::BITS && *self >> bits != 0 {
+                    return Err(TMValueError::BadValue);
+                }
+                Ok(*self as u64)
+            }
+            fn from_bits(raw: u64, _bits: u32) -> Result<Self, TMValueError> {
+                Ok(raw as Self)
+            }
+        }
+    };
+}
+macro_rules! signed_bit_packed {
+    ($type:ident) => {
+        impl BitPacked for $type {
+            const BITS: u32 = $type::BITS;
+            const LAYOUT_HASH: u64 = layout_hash("Signed", &[size_of::<Self>() as u64]);
+            #[cfg(feature = "ground")]
+            const PRIMITIVE: crate::schema::Primitive = crate::schema::Primitive::Signed {
+                bytes: size_of::<Self>() as u8,
+            };
+            fn to_bits(&self, bits: u32) -> Result<u64, TMValueError> {
+                // two's complement, the value has to survive sign extension
+                let unused = Self::BITS - bits;
+                if (*self << unused) >> unused != *self {
+                    return Err(TMValueError::BadValue);
+                }
+                Ok(*self as u64 & (u64::MAX >> (64 - bits)))
+            }
+            fn from_bits(raw: u64, bits: u32) -> Result<Self, TMValueError> {
+                let unused = Self::BITS - bits;
+                Ok(((raw as Self) << unused) >> unused)
+            }
+        }
+    };
+}
+unsigned_bit_packed!(u8);
+unsigned_bit_packed!(u16);
+unsigned_bit_packed!(u32);
+unsigned_bit_packed!(u64);
+signed_bit_packed!(i8);
+signed_bit_packed!(i16);
+signed_bit_packed!(i32);
+signed_bit_packed!(i64);
+
+/// Packs values of arbitrary bit width into a byte string
+pub struct BitWriter<'a> {
+    mem: &'a mut [u8],
+    order: BitOrder,
+    pos: usize,
+}
+impl<'a> BitWriter<'a> {
+    /// Clears `mem`, padding bits stay zero
+    pub fn new(mem: &'a mut [u8], order: BitOrder) -> Self {
+        mem.fill(0);
+        Self { mem, order, pos: 0 }
+    }
+    pub fn write(&mut self, raw: u64, bits: u32) {
+        for i in 0..bits {
+            if (raw >> self.order.bit_of_value(i, bits)) & 1 == 1 {
+                self.mem[self.pos / 8] |= self.order.mask(self.pos);
+            }
+            self.pos += 1;
+        }
+    }
+}
+
+/// Reads values written by a [`BitWriter`]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    order: BitOrder,
+    pos: usize,
+}
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8], order: BitOrder) -> Self {
+        Self {
+            bytes,
+            order,
+            pos: 0,
+        }
+    }
+    pub fn read(&mut self, bits: u32) -> u64 {
+        let mut raw = 0;
+        for i in 0..bits {
+            if self.bytes[self.pos / 8] & self.order.mask(self.pos) != 0 {
+                raw |= 1 << self.order.bit_of_value(i, bits);
+            }
+            self.pos += 1;
+        }
+        raw
+    }
+    /// Rejects set padding bits so every value has exactly one encoding
+    pub fn finish(self) -> Result<(), TMValueError> {
+        for pos in self.pos..self.bytes.len() * 8 {
+            if self.bytes[pos / 8] & self.order.mask(pos) != 0 {
+                return Err(TMValueError::BadValue);
+            }
+        }
+        Ok(())
+    }
+}
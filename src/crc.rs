@@ -0,0 +1,134 @@
+//! Table driven checksums, the tables are generated at compile time
+
+/// Checksum algorithms selectable per beacon
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrcKind {
+    /// CRC-16/CCITT-FALSE, poly 0x1021, init 0xFFFF
+    Ccitt16,
+    /// CRC-16/X25, reflected poly 0x1021, init and xorout 0xFFFF
+    X25,
+    /// CRC-32C (Castagnoli), reflected poly 0x1EDC6F41, init and xorout 0xFFFFFFFF
+    Crc32c,
+}
+impl CrcKind {
+    /// Width of the checksum in bytes
+    pub const fn size(&self) -> usize {
+        match self {
+            Self::Ccitt16 | Self::X25 => 2,
+            Self::Crc32c => 4,
+        }
+    }
+    pub fn checksum(&self, bytes: &[u8]) -> u32 {
+        match self {
+            Self::Ccitt16 => ccitt16(bytes) as u32,
+            Self::X25 => x25(bytes) as u32,
+            Self::Crc32c => crc32c(bytes),
+        }
+    }
+    /// Writes the little endian checksum of `bytes` into `mem`
+    pub fn write(&self, bytes: &[u8], mem: &mut [u8]) {
+        let size = self.size();
+        mem[..size].copy_from_slice(&self.checksum(bytes).to_le_bytes()[..size]);
+    }
+    /// Reads a little endian checksum written by [`CrcKind::write`]
+    pub fn read(&self, mem: &[u8]) -> u32 {
+        let mut bytes = [0u8; 4];
+        bytes[..self.size()].copy_from_slice(&mem[..self.size()]);
+        u32::from_le_bytes(bytes)
+    }
+}
+
+const fn table16(poly: u16) -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ poly
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn table16_reflected(poly: u16) -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u16;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn table32_reflected(poly: u32) -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const CCITT16_TABLE: [u16; 256] = table16(0x1021);
+const X25_TABLE: [u16; 256] = table16_reflected(0x8408);
+const CRC32C_TABLE: [u32; 256] = table32_reflected(0x82F63B78);
+
+pub const fn ccitt16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    let mut i = 0;
+    while i < bytes.len() {
+        crc = (crc << 8) ^ CCITT16_TABLE[((crc >> 8) as u8 ^ bytes[i]) as usize];
+        i += 1;
+    }
+    crc
+}
+
+pub const fn x25(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    let mut i = 0;
+    while i < bytes.len() {
+        crc = (crc >> 8) ^ X25_TABLE[(crc as u8 ^ bytes[i]) as usize];
+        i += 1;
+    }
+    crc ^ 0xFFFF
+}
+
+pub const fn crc32c(bytes: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFFFFFF;
+    let mut i = 0;
+    while i < bytes.len() {
+        crc = (crc >> 8) ^ CRC32C_TABLE[(crc as u8 ^ bytes[i]) as usize];
+        i += 1;
+    }
+    crc ^ 0xFFFFFFFF
+}
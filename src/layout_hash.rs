@@ -0,0 +1,37 @@
+//! Stable compile time hashes of wire layouts, used to detect flight and ground
+//! builds disagreeing on the telemetry dictionary
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64 bit FNV-1a of `bytes`, continuing from `hash`
+const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// Hash of a layout of the given kind made of `parts`, e.g. the hashes of its fields
+pub const fn layout_hash(kind: &str, parts: &[u64]) -> u64 {
+    let mut hash = fnv1a(FNV_OFFSET, kind.as_bytes());
+    let mut i = 0;
+    while i < parts.len() {
+        hash = fnv1a(hash, &parts[i].to_le_bytes());
+        i += 1;
+    }
+    hash
+}
+
+/// Hash of a string, e.g. an address
+pub const fn str_hash(value: &str) -> u64 {
+    fnv1a(FNV_OFFSET, value.as_bytes())
+}
+
+/// 32 bit hash sent in beacon headers
+pub const fn fold_hash(hash: u64) -> u32 {
+    (hash ^ (hash >> 32)) as u32
+}
@@ -1,44 +1,149 @@
 #![no_std]
 #![feature(const_trait_impl)]
+#![feature(const_cmp)]
+#![feature(array_try_from_fn)]
 
 #[cfg(feature = "ground")]
 extern crate alloc;
+// lets derived types inside this crate use the same paths as downstream crates
+extern crate self as tmtc_system;
 
 mod bitfield;
+pub mod crc;
+mod layout_hash;
+pub mod pus;
+mod scaled;
+#[cfg(feature = "ground")]
+pub mod schema;
+pub mod space_packet;
+mod telecommand;
 mod telemetry_container;
 mod telemetry_value;
+pub mod transfer_frame;
+mod verification_report;
+#[cfg(feature = "ground")]
+pub mod xtce;
 
 // macro reexports
 pub use macros::TMValue;
 pub use macros::beacon;
+pub use macros::telecommand_definition;
 pub use macros::telemetry_definition;
 
 // value reexports
+pub use scaled::{Scaled, ScaledRaw};
 pub use telemetry_value::TMValue;
 pub use telemetry_value::TMValueError;
+pub use telemetry_value::{BigEndian, LittleEndian};
+
+#[cfg(feature = "heapless")]
+pub use heapless;
 
 // container reexports
 pub use telemetry_container::TelemetryContainer;
 pub use telemetry_container::UnsupportedValue;
 pub use telemetry_container::ceil_to_fd_compat;
 
+// telecommand reexports
+pub use telecommand::TELECOMMAND_HEADER_SIZE;
+pub use telecommand::Telecommand;
+pub use telecommand::TelecommandDispatcher;
+pub use telecommand::TelecommandHandler;
+pub use telecommand::TelecommandHeader;
+
+// verification reexports
+pub use verification_report::ExecutionReport;
+pub use verification_report::FailureCode;
+pub use verification_report::FailureReport;
+pub use verification_report::ProgressReport;
+
 pub const trait TelemetryDefinition {
     fn id(&self) -> u16;
     fn address(&self) -> &str;
 }
 
+pub const trait TelecommandDefinition {
+    fn id(&self) -> u16;
+    fn address(&self) -> &str;
+}
+
+/// Built-in command verification telemetry, similar to PUS service 1.
+/// The ids start at 0xFF00 so they can share a beacon with application telemetry
+#[telemetry_definition(id = 65280, address = crate)]
+mod verification {
+    #[tmv(crate::ExecutionReport)]
+    struct AcceptanceSuccess;
+    #[tmv(crate::FailureReport)]
+    struct AcceptanceFailure;
+    #[tmv(crate::ExecutionReport)]
+    struct StartSuccess;
+    #[tmv(crate::FailureReport)]
+    struct StartFailure;
+    #[tmv(crate::ProgressReport)]
+    struct Progress;
+    #[tmv(crate::ExecutionReport)]
+    struct CompletionSuccess;
+    #[tmv(crate::FailureReport)]
+    struct CompletionFailure;
+}
+
 #[cfg(feature = "ground")]
 pub use crate::telemetry_value::ground_tm;
 /// Reexports that should only be used by the macro generated code
 pub mod _internal {
     use crate::TMValue;
-    pub use crate::bitfield::Bitfield;
+    pub use crate::bitfield::{BitOrder, BitPacked, BitReader, BitWriter, Bitfield};
     #[cfg(feature = "ground")]
     pub use crate::ground_tm::*;
+    pub use crate::layout_hash::{fold_hash, layout_hash, str_hash};
+    #[cfg(feature = "ground")]
+    pub use crate::schema::{BeaconSchema, TelemetrySchema};
+    pub use crate::{BigEndian, LittleEndian};
     pub const trait InternalTelemetryDefinition: crate::TelemetryDefinition {
         type TMValueType: crate::TMValue;
         const MAX_BYTE_SIZE: usize = Self::TMValueType::MAX_BYTE_SIZE;
         const ID: u16;
+        const ADDRESS: &'static str;
+        /// Hash of the id, address and value layout
+        const HASH: u64 = layout_hash(
+            "Definition",
+            &[
+                Self::ID as u64,
+                str_hash(Self::ADDRESS),
+                Self::TMValueType::LAYOUT_HASH,
+            ],
+        );
+        /// Address endings and functions of the ground calibrations
+        #[cfg(feature = "ground")]
+        const CALIBRATIONS: &'static [(&'static str, &'static str)] = &[];
+    }
+    /// Schema of the definition `D`
+    #[cfg(feature = "ground")]
+    pub fn definition_schema<D: InternalTelemetryDefinition>() -> crate::schema::DefinitionSchema {
+        D::CALIBRATIONS.iter().fold(
+            crate::schema::DefinitionSchema::new(D::ID, D::ADDRESS, D::TMValueType::schema()),
+            |schema, (name, function)| schema.with_calibration(name, function),
+        )
+    }
+    pub const trait InternalTelecommandDefinition: crate::TelecommandDefinition {
+        type TCValueType: crate::TMValue;
+        const MAX_BYTE_SIZE: usize = Self::TCValueType::MAX_BYTE_SIZE;
+        const ID: u16;
+        const ADDRESS: &'static str;
+    }
+    /// Reads the value of the definition `D` at `pos`, locating errors in the frame
+    pub fn read_definition<D: InternalTelemetryDefinition>(
+        bytes: &[u8],
+        pos: usize,
+    ) -> Result<(usize, D::TMValueType), crate::ParseError> {
+        let value = bytes.get(pos..).unwrap_or(&[]);
+        D::TMValueType::read(value).map_err(|error| crate::ParseError::InvalidValue {
+            address: D::ADDRESS,
+            offset: pos,
+            expected: D::MAX_BYTE_SIZE,
+            available: value.len(),
+            error,
+        })
     }
 }
 
@@ -50,30 +155,168 @@ pub struct NotFoundError;
 pub enum BeaconOperationError {
     DefNotInBeacon,
     OutOfMemory,
+    /// The value with this address could not be written, see [`TMValue::write`]
+    InvalidValue {
+        address: &'static str,
+        error: TMValueError,
+    },
 }
 
 #[derive(Debug)]
-pub enum ParseError {
-    WrongId,
-    BadCRC,
+pub enum DispatchError {
+    OutOfMemory,
+    UnknownId(u16),
+    BadArguments(TMValueError),
+    /// Number of bytes left after the arguments, the frame was not built for this command
+    TrailingBytes(usize),
+    ExecutionFailed(u8),
+}
+
+#[derive(Debug)]
+pub enum SpacePacketError {
     OutOfMemory,
+    InvalidApid,
+    InvalidLength,
+    UnsupportedVersion,
+    MissingSecondaryHeader,
+    /// A value could not be written, see [`TMValue::write`]
+    InvalidValue(TMValueError),
+}
+impl From<TMValueError> for SpacePacketError {
+    fn from(error: TMValueError) -> Self {
+        match error {
+            TMValueError::OutOfMemory => Self::OutOfMemory,
+            error => Self::InvalidValue(error),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PusError {
+    OutOfMemory,
+    UnsupportedVersion,
+    UnsupportedService(u8),
+    UnsupportedSubtype(u8),
+    MalformedRequest,
+    /// The value with this address is not set, reports carry every value of the structure
+    MissingParameter(&'static str),
+    /// A value could not be written, see [`TMValue::write`]
+    InvalidValue(TMValueError),
+}
+impl From<TMValueError> for PusError {
+    fn from(error: TMValueError) -> Self {
+        match error {
+            TMValueError::OutOfMemory => Self::OutOfMemory,
+            error => Self::InvalidValue(error),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TransferFrameError {
+    InvalidLength,
+    InvalidSpacecraftId,
+    InvalidVirtualChannel,
+    InvalidPacket,
+    UnsupportedVersion,
+    BadCRC,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    WrongId {
+        expected: u16,
+        found: u16,
+    },
+    BadCRC {
+        received: u32,
+        calculated: u32,
+    },
+    /// The frame is too short for its header
+    OutOfMemory {
+        expected: usize,
+        available: usize,
+    },
+    /// The frame was sent by a build with another telemetry dictionary
+    DictionaryMismatch {
+        expected: u32,
+        found: u32,
+    },
+    /// Bytes are left after the last value, `expected` is the length of the parsed frame
+    TrailingBytes {
+        expected: usize,
+        found: usize,
+    },
+    /// A value could not be parsed, `expected` is its maximum byte size
+    InvalidValue {
+        address: &'static str,
+        offset: usize,
+        expected: usize,
+        available: usize,
+        error: TMValueError,
+    },
+}
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WrongId { expected, found } => {
+                write!(f, "wrong id: expected {}, found {}", expected, found)
+            }
+            Self::BadCRC {
+                received,
+                calculated,
+            } => write!(
+                f,
+                "bad crc: received {:#x}, calculated {:#x}",
+                received, calculated
+            ),
+            Self::OutOfMemory {
+                expected,
+                available,
+            } => write!(
+                f,
+                "frame too short: expected {} header bytes, {} available",
+                expected, available
+            ),
+            Self::DictionaryMismatch { expected, found } => write!(
+                f,
+                "dictionary mismatch: expected hash {:#010x}, found {:#010x}",
+                expected, found
+            ),
+            Self::TrailingBytes { expected, found } => write!(
+                f,
+                "trailing bytes: expected a frame of {} bytes, found {}",
+                expected, found
+            ),
+            Self::InvalidValue {
+                address,
+                offset,
+                expected,
+                available,
+                error,
+            } => write!(
+                f,
+                "could not parse {} at byte offset {} (up to {} bytes expected, {} available): {}",
+                address, offset, expected, available, error
+            ),
+        }
+    }
 }
 
 // Dynamic beacon trait
 pub trait Beacon {
-    type Timestamp;
+    type Timestamp: TMValue;
     fn insert_slice(
         &mut self,
         telemetry_definition: &dyn TelemetryDefinition,
         bytes: &[u8],
     ) -> Result<(), BeaconOperationError>;
-    fn from_bytes(
-        &mut self,
-        bytes: &[u8],
-        crc_func: &mut dyn FnMut(&[u8]) -> u16,
-    ) -> Result<(), ParseError>;
-    fn to_bytes(&mut self, crc_func: &mut dyn FnMut(&[u8]) -> u16) -> &[u8];
+    #[allow(clippy::wrong_self_convention)]
+    fn from_bytes(&mut self, bytes: &[u8]) -> Result<(), ParseError>;
+    /// Encodes the beacon, fails if a value does not fit its layout
+    fn to_bytes(&mut self) -> Result<&[u8], BeaconOperationError>;
     fn set_timestamp(&mut self, timestamp: Self::Timestamp);
+    fn timestamp(&self) -> &Self::Timestamp;
     fn flush(&mut self);
     fn name(&self) -> &'static str;
     fn id(&self) -> u8;
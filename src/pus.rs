@@ -0,0 +1,173 @@
+//! ECSS-E-ST-70-41C (PUS-C) housekeeping service for beacons built with `pus = true`.
+//! The beacon id is the structure id. TM[3,25] reports carry a [`CucTime`] time field
+//! and the structure id followed by every value of the beacon in declaration order,
+//! most significant byte first. A report is only written once all values are set
+
+use crate::{PusError, TMValue, TMValueError};
+
+/// PUS-C version number carried in every secondary header
+pub const PUS_VERSION: u8 = 2;
+/// Housekeeping service type id
+pub const HOUSEKEEPING_SERVICE: u8 = 3;
+/// TC[3,5] enable periodic generation of housekeeping reports
+pub const ENABLE_PERIODIC_GENERATION: u8 = 5;
+/// TC[3,6] disable periodic generation of housekeeping reports
+pub const DISABLE_PERIODIC_GENERATION: u8 = 6;
+/// TM[3,25] housekeeping parameter report
+pub const HOUSEKEEPING_PARAMETER_REPORT: u8 = 25;
+/// TC[3,31] modify the collection interval of housekeeping reports
+pub const MODIFY_COLLECTION_INTERVAL: u8 = 31;
+
+/// CCSDS unsegmented time code with 4 octets of seconds and 2 octets of binary fractions
+/// of a second, sent without P-field
+#[derive(TMValue, Debug, Default, Clone, Copy, PartialEq)]
+#[tm(endian = big)]
+pub struct CucTime {
+    pub coarse: u32,
+    pub fine: u16,
+}
+impl CucTime {
+    pub fn new(coarse: u32, fine: u16) -> Self {
+        Self { coarse, fine }
+    }
+}
+
+/// ECSS-E-ST-70-41C telemetry packet secondary header without the time field
+pub struct PusTmSecondaryHeader {
+    pub service: u8,
+    pub subtype: u8,
+    pub message_counter: u16,
+    pub destination_id: u16,
+}
+impl PusTmSecondaryHeader {
+    /// Writes the header followed by the given time field
+    pub fn write(&self, time: &CucTime, mem: &mut [u8]) -> Result<usize, PusError> {
+        let header = mem.get_mut(..7).ok_or(PusError::OutOfMemory)?;
+        header[0] = PUS_VERSION << 4;
+        header[1] = self.service;
+        header[2] = self.subtype;
+        header[3..5].copy_from_slice(&self.message_counter.to_be_bytes());
+        header[5..7].copy_from_slice(&self.destination_id.to_be_bytes());
+        let len = time.write(&mut mem[7..])?;
+        Ok(7 + len)
+    }
+}
+
+/// ECSS-E-ST-70-41C telecommand packet secondary header
+pub struct PusTcSecondaryHeader {
+    pub acknowledgement_flags: u8,
+    pub service: u8,
+    pub subtype: u8,
+    pub source_id: u16,
+}
+impl PusTcSecondaryHeader {
+    pub const SIZE: usize = 5;
+    pub fn read(bytes: &[u8]) -> Result<Self, PusError> {
+        let header = bytes.get(..Self::SIZE).ok_or(PusError::OutOfMemory)?;
+        if header[0] >> 4 != PUS_VERSION {
+            return Err(PusError::UnsupportedVersion);
+        }
+        Ok(Self {
+            acknowledgement_flags: header[0] & 0x0F,
+            service: header[1],
+            subtype: header[2],
+            source_id: u16::from_be_bytes([header[3], header[4]]),
+        })
+    }
+    pub fn write(&self, mem: &mut [u8]) -> Result<usize, PusError> {
+        let header = mem.get_mut(..Self::SIZE).ok_or(PusError::OutOfMemory)?;
+        header[0] = (PUS_VERSION << 4) | (self.acknowledgement_flags & 0x0F);
+        header[1] = self.service;
+        header[2] = self.subtype;
+        header[3..5].copy_from_slice(&self.source_id.to_be_bytes());
+        Ok(Self::SIZE)
+    }
+}
+
+/// State of the periodic housekeeping report of one structure id.
+/// The collection interval is counted in calls to [`Housekeeping::tick`]
+pub struct Housekeeping {
+    structure_id: u16,
+    enabled: bool,
+    interval: u32,
+    elapsed: u32,
+    message_counter: u16,
+}
+impl Housekeeping {
+    pub fn new(structure_id: u16) -> Self {
+        Self {
+            structure_id,
+            enabled: false,
+            interval: 1,
+            elapsed: 0,
+            message_counter: 0,
+        }
+    }
+    pub fn structure_id(&self) -> u16 {
+        self.structure_id
+    }
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    pub fn interval(&self) -> u32 {
+        self.interval
+    }
+    /// Applies a TC[3,5], TC[3,6] or TC[3,31] request given its subtype and application data.
+    /// The whole request is validated first, a malformed request changes nothing.
+    /// Returns whether the request referenced this structure id
+    pub fn handle_request(&mut self, subtype: u8, data: &[u8]) -> Result<bool, PusError> {
+        let entry_size = match subtype {
+            ENABLE_PERIODIC_GENERATION | DISABLE_PERIODIC_GENERATION => 2,
+            MODIFY_COLLECTION_INTERVAL => 6,
+            _ => return Err(PusError::UnsupportedSubtype(subtype)),
+        };
+        let (&count, mut data) = data.split_first().ok_or(PusError::MalformedRequest)?;
+        let mut referenced = false;
+        let mut interval = None;
+        for _ in 0..count {
+            let entry = data.get(..entry_size).ok_or(PusError::MalformedRequest)?;
+            data = &data[entry_size..];
+            let sid = u16::from_be_bytes([entry[0], entry[1]]);
+            if subtype == MODIFY_COLLECTION_INTERVAL {
+                let value = u32::from_be_bytes(entry[2..6].try_into().unwrap());
+                if value == 0 {
+                    return Err(PusError::MalformedRequest);
+                }
+                if sid == self.structure_id {
+                    interval = Some(value);
+                }
+            }
+            referenced |= sid == self.structure_id;
+        }
+        if referenced {
+            match interval {
+                Some(interval) => self.interval = interval,
+                None => self.enabled = subtype == ENABLE_PERIODIC_GENERATION,
+            }
+            self.elapsed = 0;
+        }
+        Ok(referenced)
+    }
+    /// Advances the collection interval, returns true when a report is due
+    pub fn tick(&mut self) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        self.elapsed += 1;
+        if self.elapsed >= self.interval {
+            self.elapsed = 0;
+            return true;
+        }
+        false
+    }
+    /// Message type counter the next TM[3,25] report will carry
+    pub fn message_counter(&self) -> u16 {
+        self.message_counter
+    }
+    /// Message type counter for the next TM[3,25] report
+    pub fn next_message_counter(&mut self) -> u16 {
+        let counter = self.message_counter;
+        self.message_counter = self.message_counter.wrapping_add(1);
+        counter
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{TMValue, TMValueError};
+
+/// Integers that can carry a [`Scaled`] value
+pub trait ScaledRaw: Copy {
+    fn to_f32(self) -> f32;
+    /// Rounds to the nearest integer, none if out of range
+    fn from_f32(value: f32) -> Option<Self>;
+}
+macro_rules! scaled_raw {
+    ($type:ident) => {
+        impl ScaledRaw for $type {
+            fn to_f32(self) -> f32 {
+                self as f32
+            }
+            fn from_f32(value: f32) -> Option<Self> {
+                // f32::round is not available in core
+                let rounded = if value < 0. { value - 0.5 } else { value + 0.5 };
+                if !(rounded > $type::MIN as f32 - 1. && rounded < $type::MAX as f32 + 1.) {
+                    return None;
+                }
+                Some(rounded as $type)
+            }
+        }
+    };
+}
+scaled_raw!(u8);
+scaled_raw!(u16);
+scaled_raw!(u32);
+scaled_raw!(i8);
+scaled_raw!(i16);
+scaled_raw!(i32);
+
+/// Fixed point value sent as the raw integer `T`.
+/// The engineering value is `raw / SCALE + OFFSET`, e.g. centi-degrees
+/// celsius are `Scaled<i16, 100>` and centi-kelvin `Scaled<u16, 100, -273>`
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Scaled<T, const SCALE: u32, const OFFSET: i32 = 0> {
+    raw: T,
+}
+impl<T: ScaledRaw, const SCALE: u32, const OFFSET: i32> Scaled<T, SCALE, OFFSET> {
+    /// Converts the engineering value, fails if the raw value does not fit into `T`
+    pub fn new(value: f32) -> Result<Self, TMValueError> {
+        const { assert!(SCALE != 0, "scale should not be zero") };
+        let raw =
+            T::from_f32((value - OFFSET as f32) * SCALE as f32).ok_or(TMValueError::BadValue)?;
+        Ok(Self { raw })
+    }
+    pub const fn from_raw(raw: T) -> Self {
+        Self { raw }
+    }
+    pub const fn raw(&self) -> T {
+        self.raw
+    }
+    /// Engineering value
+    pub fn value(&self) -> f32 {
+        const { assert!(SCALE != 0, "scale should not be zero") };
+        self.raw.to_f32() / SCALE as f32 + OFFSET as f32
+    }
+}
+impl<T: TMValue, const SCALE: u32, const OFFSET: i32> TMValue for Scaled<T, SCALE, OFFSET> {
+    const MAX_BYTE_SIZE: usize = T::MAX_BYTE_SIZE;
+    const LAYOUT_HASH: u64 = crate::layout_hash::layout_hash(
+        "Scaled",
+        &[T::LAYOUT_HASH, SCALE as u64, OFFSET as i64 as u64],
+    );
+    #[cfg(feature = "ground")]
+    fn schema() -> crate::schema::TypeSchema {
+        crate::schema::TypeSchema::Scaled {
+            raw: alloc::boxed::Box::new(T::schema()),
+            scale: SCALE,
+            offset: OFFSET,
+        }
+    }
+    fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        let (len, raw) = T::read(bytes)?;
+        Ok((len, Self { raw }))
+    }
+    fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        self.raw.write(mem)
+    }
+    fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        let (len, raw) = T::read_be(bytes)?;
+        Ok((len, Self { raw }))
+    }
+    fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        self.raw.write_be(mem)
+    }
+}
+
+// the ground receives the engineering value
+#[cfg(feature = "ground")]
+impl<T: ScaledRaw, const SCALE: u32, const OFFSET: i32> serde::Serialize
+    for Scaled<T, SCALE, OFFSET>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(self.value())
+    }
+}
+#[cfg(feature = "ground")]
+impl<'de, T: ScaledRaw, const SCALE: u32, const OFFSET: i32> serde::Deserialize<'de>
+    for Scaled<T, SCALE, OFFSET>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = f32::deserialize(deserializer)?;
+        Self::new(value).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,733 @@
+//! Machine readable layout of telemetry, emitted by the macros for ground tools.
+//! A [`TypeSchema`] is enough to decode a value into a [`DynamicValue`]
+//! without the rust type, so schemas can be shipped as files next to new flight software
+
+use crate::crc::CrcKind;
+use crate::{TMValue, TMValueError};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub use crate::bitfield::BitOrder;
+
+/// Scalar encodings, integers and floats are sent in `bytes` bytes
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Primitive {
+    Bool,
+    /// Unicode scalar value as `u32`
+    Char,
+    Unsigned {
+        bytes: u8,
+    },
+    Signed {
+        bytes: u8,
+    },
+    Float {
+        bytes: u8,
+    },
+}
+
+/// Wire layout of a [`TMValue`]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeSchema {
+    /// Zero sized types
+    Unit,
+    Primitive(Primitive),
+    Array {
+        len: usize,
+        element: Box<TypeSchema>,
+    },
+    /// Up to `capacity` elements after a `length_bytes` wide length prefix
+    Vec {
+        capacity: usize,
+        length_bytes: u8,
+        element: Box<TypeSchema>,
+    },
+    /// Up to `capacity` utf-8 bytes after a `length_bytes` wide length prefix
+    String {
+        capacity: usize,
+        length_bytes: u8,
+    },
+    /// Tag byte 0 or 1 followed by the value
+    Option(Box<TypeSchema>),
+    /// The value is encoded in the given byte order, whatever the surrounding type uses
+    ByteOrder {
+        big_endian: bool,
+        value: Box<TypeSchema>,
+    },
+    /// Engineering value `raw / scale + offset`
+    Scaled {
+        raw: Box<TypeSchema>,
+        scale: u32,
+        offset: i32,
+    },
+    Struct {
+        name: String,
+        fields: Vec<FieldSchema>,
+    },
+    Enum {
+        name: String,
+        tag: Primitive,
+        /// Byte order of the tag, `None` if it follows the surrounding type
+        big_endian: Option<bool>,
+        variants: Vec<VariantSchema>,
+    },
+    /// `#[tm(packed)]` struct, fields are packed into bits
+    Packed {
+        name: String,
+        bit_order: BitOrder,
+        fields: Vec<PackedFieldSchema>,
+    },
+    /// Types without a schema, decoded as `size` raw bytes
+    Opaque {
+        size: usize,
+    },
+}
+
+/// Field of a struct or enum variant. Skipped fields are not on the wire and not listed
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FieldSchema {
+    /// Field name, the index for tuple fields
+    pub name: String,
+    /// `None` if the field follows the byte order of the surrounding type
+    pub big_endian: Option<bool>,
+    pub schema: TypeSchema,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VariantSchema {
+    pub name: String,
+    pub tag: i128,
+    pub fields: Vec<FieldSchema>,
+}
+
+/// Field of a packed struct, `primitive` is bool or an integer
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PackedFieldSchema {
+    pub name: String,
+    pub bits: u32,
+    pub primitive: Primitive,
+}
+
+impl TypeSchema {
+    /// Schema of `T`, shorthand for the macro generated code
+    pub fn of<T: TMValue>() -> Self {
+        T::schema()
+    }
+    pub fn structure(name: &str, fields: impl Into<Vec<FieldSchema>>) -> Self {
+        Self::Struct {
+            name: name.into(),
+            fields: fields.into(),
+        }
+    }
+    pub fn enumeration(
+        name: &str,
+        tag: Primitive,
+        big_endian: Option<bool>,
+        variants: impl Into<Vec<VariantSchema>>,
+    ) -> Self {
+        Self::Enum {
+            name: name.into(),
+            tag,
+            big_endian,
+            variants: variants.into(),
+        }
+    }
+    pub fn packed(
+        name: &str,
+        bit_order: BitOrder,
+        fields: impl Into<Vec<PackedFieldSchema>>,
+    ) -> Self {
+        Self::Packed {
+            name: name.into(),
+            bit_order,
+            fields: fields.into(),
+        }
+    }
+    /// Largest number of bytes the value can take, see [`TMValue::MAX_BYTE_SIZE`].
+    /// `None` if the size overflows, which only happens for hostile schemas
+    pub fn max_size(&self) -> Option<usize> {
+        let fields_size = |fields: &[FieldSchema]| {
+            fields
+                .iter()
+                .try_fold(0usize, |size, f| size.checked_add(f.schema.max_size()?))
+        };
+        match self {
+            Self::Unit => Some(0),
+            Self::Primitive(primitive) => Some(primitive.size()),
+            Self::Array { len, element } => len.checked_mul(element.max_size()?),
+            Self::Vec {
+                capacity,
+                length_bytes,
+                element,
+            } => capacity
+                .checked_mul(element.max_size()?)?
+                .checked_add(*length_bytes as usize),
+            Self::String {
+                capacity,
+                length_bytes,
+            } => capacity.checked_add(*length_bytes as usize),
+            Self::Option(value) => value.max_size()?.checked_add(1),
+            Self::ByteOrder { value, .. } => value.max_size(),
+            Self::Scaled { raw, .. } => raw.max_size(),
+            Self::Struct { fields, .. } => fields_size(fields),
+            Self::Enum { tag, variants, .. } => variants
+                .iter()
+                .try_fold(0usize, |size, v| Some(size.max(fields_size(&v.fields)?)))?
+                .checked_add(tag.size()),
+            Self::Packed { fields, .. } => {
+                let bits: u64 = fields.iter().map(|f| f.bits as u64).sum();
+                usize::try_from(bits.div_ceil(8)).ok()
+            }
+            Self::Opaque { size } => Some(*size),
+        }
+    }
+    /// Decodes a value encoded with [`TMValue::write`], returns the number of bytes read
+    pub fn decode(&self, bytes: &[u8]) -> Result<(usize, DynamicValue), TMValueError> {
+        self.decode_with(bytes, false)
+    }
+    /// `big_endian` selects between [`TMValue::read`] and [`TMValue::read_be`]
+    fn decode_with(
+        &self,
+        bytes: &[u8],
+        big_endian: bool,
+    ) -> Result<(usize, DynamicValue), TMValueError> {
+        match self {
+            Self::Unit => Ok((0, DynamicValue::Unit)),
+            Self::Primitive(primitive) => primitive.decode(bytes, big_endian),
+            Self::Array { len, element } => decode_list(bytes, 0, *len, element, big_endian),
+            Self::Vec {
+                capacity,
+                length_bytes,
+                element,
+            } => {
+                let (pos, len) = decode_length(bytes, *capacity, *length_bytes, big_endian)?;
+                decode_list(bytes, pos, len, element, big_endian)
+            }
+            Self::String {
+                capacity,
+                length_bytes,
+            } => {
+                let (pos, len) = decode_length(bytes, *capacity, *length_bytes, big_endian)?;
+                let utf8 = bytes
+                    .get(pos..pos.saturating_add(len))
+                    .ok_or(TMValueError::OutOfMemory)?;
+                let string = core::str::from_utf8(utf8).map_err(|_| TMValueError::InvalidUtf8)?;
+                Ok((pos + len, DynamicValue::String(string.into())))
+            }
+            Self::Option(value) => match bytes.first().ok_or(TMValueError::OutOfMemory)? {
+                0u8 => Ok((1, DynamicValue::Option(None))),
+                1u8 => {
+                    let (len, value) = value.decode_with(&bytes[1..], big_endian)?;
+                    Ok((1 + len, DynamicValue::Option(Some(Box::new(value)))))
+                }
+                _ => Err(TMValueError::BadEnumVariant),
+            },
+            Self::ByteOrder { big_endian, value } => value.decode_with(bytes, *big_endian),
+            Self::Scaled { raw, scale, offset } => {
+                let (len, raw) = raw.decode_with(bytes, big_endian)?;
+                let raw = match raw {
+                    DynamicValue::Unsigned(raw) => raw as f32,
+                    DynamicValue::Signed(raw) => raw as f32,
+                    _ => return Err(TMValueError::BadValue),
+                };
+                // same precision as `Scaled::value`
+                let value = raw / *scale as f32 + *offset as f32;
+                Ok((len, DynamicValue::Float(value as f64)))
+            }
+            Self::Struct { fields, .. } => {
+                let (pos, fields) = decode_fields(bytes, 0, fields, big_endian)?;
+                Ok((pos, DynamicValue::Struct(fields)))
+            }
+            Self::Enum {
+                tag,
+                big_endian: tag_big_endian,
+                variants,
+                ..
+            } => {
+                let (pos, tag) = match tag.decode(bytes, tag_big_endian.unwrap_or(big_endian))? {
+                    (pos, DynamicValue::Unsigned(tag)) => (pos, tag as i128),
+                    (pos, DynamicValue::Signed(tag)) => (pos, tag),
+                    _ => return Err(TMValueError::BadValue),
+                };
+                let variant = variants
+                    .iter()
+                    .find(|v| v.tag == tag)
+                    .ok_or(TMValueError::BadEnumVariant)?;
+                let (pos, fields) = decode_fields(bytes, pos, &variant.fields, big_endian)?;
+                Ok((
+                    pos,
+                    DynamicValue::Enum {
+                        variant: variant.name.clone(),
+                        fields,
+                    },
+                ))
+            }
+            Self::Packed {
+                bit_order, fields, ..
+            } => {
+                if fields.iter().any(|f| f.bits == 0 || f.bits > 64) {
+                    return Err(TMValueError::BadValue);
+                }
+                let size = self.max_size().ok_or(TMValueError::BadValue)?;
+                let bytes = bytes.get(..size).ok_or(TMValueError::OutOfMemory)?;
+                let mut reader = crate::bitfield::BitReader::new(bytes, *bit_order);
+                let mut values = Vec::with_capacity(fields.len());
+                for field in fields {
+                    let raw = reader.read(field.bits);
+                    values.push((field.name.clone(), field.primitive.unpack(raw, field.bits)?));
+                }
+                reader.finish()?;
+                Ok((size, DynamicValue::Struct(values)))
+            }
+            Self::Opaque { size } => {
+                let bytes = bytes.get(..*size).ok_or(TMValueError::OutOfMemory)?;
+                Ok((*size, DynamicValue::Bytes(bytes.into())))
+            }
+        }
+    }
+}
+
+impl FieldSchema {
+    pub fn new(name: &str, big_endian: Option<bool>, schema: TypeSchema) -> Self {
+        Self {
+            name: name.into(),
+            big_endian,
+            schema,
+        }
+    }
+}
+
+impl VariantSchema {
+    pub fn new(name: &str, tag: i128, fields: impl Into<Vec<FieldSchema>>) -> Self {
+        Self {
+            name: name.into(),
+            tag,
+            fields: fields.into(),
+        }
+    }
+}
+
+impl PackedFieldSchema {
+    pub fn new(name: &str, bits: u32, primitive: Primitive) -> Self {
+        Self {
+            name: name.into(),
+            bits,
+            primitive,
+        }
+    }
+}
+
+impl Primitive {
+    /// Encoded width in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Bool => 1,
+            Self::Char => 4,
+            Self::Unsigned { bytes } | Self::Signed { bytes } | Self::Float { bytes } => {
+                *bytes as usize
+            }
+        }
+    }
+    fn decode(
+        &self,
+        bytes: &[u8],
+        big_endian: bool,
+    ) -> Result<(usize, DynamicValue), TMValueError> {
+        let size = self.size();
+        // schemas can come from untrusted sources, keep the shifts below in range
+        if size == 0 || size > 16 {
+            return Err(TMValueError::BadValue);
+        }
+        let bytes = bytes.get(..size).ok_or(TMValueError::OutOfMemory)?;
+        let raw = if big_endian {
+            bytes.iter().fold(0u128, |raw, b| (raw << 8) | *b as u128)
+        } else {
+            bytes
+                .iter()
+                .rev()
+                .fold(0u128, |raw, b| (raw << 8) | *b as u128)
+        };
+        let value = match self {
+            Self::Bool => match raw {
+                0 => DynamicValue::Bool(false),
+                1 => DynamicValue::Bool(true),
+                _ => return Err(TMValueError::BadValue),
+            },
+            Self::Char => {
+                DynamicValue::Char(char::from_u32(raw as u32).ok_or(TMValueError::BadValue)?)
+            }
+            Self::Unsigned { .. } => DynamicValue::Unsigned(raw),
+            Self::Signed { .. } => {
+                let unused = 128 - 8 * size as u32;
+                DynamicValue::Signed(((raw << unused) as i128) >> unused)
+            }
+            Self::Float { bytes: 4 } => DynamicValue::Float(f32::from_bits(raw as u32) as f64),
+            Self::Float { bytes: 8 } => DynamicValue::Float(f64::from_bits(raw as u64)),
+            Self::Float { .. } => return Err(TMValueError::BadValue),
+        };
+        Ok((size, value))
+    }
+    /// Value of a packed field, see [`crate::_internal::BitPacked::from_bits`]
+    fn unpack(&self, raw: u64, bits: u32) -> Result<DynamicValue, TMValueError> {
+        if bits == 0 || bits > 64 {
+            return Err(TMValueError::BadValue);
+        }
+        match self {
+            Self::Bool => match raw {
+                0 => Ok(DynamicValue::Bool(false)),
+                1 => Ok(DynamicValue::Bool(true)),
+                _ => Err(TMValueError::BadValue),
+            },
+            Self::Unsigned { .. } => Ok(DynamicValue::Unsigned(raw as u128)),
+            Self::Signed { .. } => {
+                let unused = 64 - bits;
+                Ok(DynamicValue::Signed(
+                    (((raw << unused) as i64) >> unused) as i128,
+                ))
+            }
+            Self::Char | Self::Float { .. } => Err(TMValueError::BadValue),
+        }
+    }
+}
+
+fn rest(bytes: &[u8], pos: usize) -> Result<&[u8], TMValueError> {
+    bytes.get(pos..).ok_or(TMValueError::OutOfMemory)
+}
+
+fn decode_length(
+    bytes: &[u8],
+    capacity: usize,
+    length_bytes: u8,
+    big_endian: bool,
+) -> Result<(usize, usize), TMValueError> {
+    let (pos, len) = Primitive::Unsigned {
+        bytes: length_bytes,
+    }
+    .decode(bytes, big_endian)?;
+    let DynamicValue::Unsigned(len) = len else {
+        unreachable!()
+    };
+    if len > capacity as u128 {
+        return Err(TMValueError::TooLong);
+    }
+    Ok((pos, len as usize))
+}
+
+/// Largest number of zero sized elements of an array or vector. They take no bytes,
+/// so the frame length does not bound their count
+const MAX_ZERO_SIZED_ELEMENTS: usize = u16::MAX as usize;
+
+/// `count` elements starting at `pos`. Schemas can come from untrusted sources,
+/// the count is only trusted as far as the bytes go
+fn decode_list(
+    bytes: &[u8],
+    mut pos: usize,
+    count: usize,
+    element: &TypeSchema,
+    big_endian: bool,
+) -> Result<(usize, DynamicValue), TMValueError> {
+    let mut values = Vec::with_capacity(count.min(bytes.len()));
+    for _ in 0..count {
+        let (len, value) = element.decode_with(rest(bytes, pos)?, big_endian)?;
+        if len == 0 && count > MAX_ZERO_SIZED_ELEMENTS {
+            return Err(TMValueError::BadValue);
+        }
+        pos += len;
+        values.push(value);
+    }
+    Ok((pos, DynamicValue::List(values)))
+}
+
+fn decode_fields(
+    bytes: &[u8],
+    mut pos: usize,
+    fields: &[FieldSchema],
+    big_endian: bool,
+) -> Result<(usize, Vec<(String, DynamicValue)>), TMValueError> {
+    let mut values = Vec::with_capacity(fields.len());
+    for field in fields {
+        let (len, value) = field
+            .schema
+            .decode_with(rest(bytes, pos)?, field.big_endian.unwrap_or(big_endian))?;
+        pos += len;
+        values.push((field.name.clone(), value));
+    }
+    Ok((pos, values))
+}
+
+/// Value decoded from a [`TypeSchema`]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DynamicValue {
+    Unit,
+    Bool(bool),
+    Char(char),
+    Unsigned(u128),
+    Signed(i128),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    /// Arrays and bounded vectors
+    List(Vec<DynamicValue>),
+    Option(Option<Box<DynamicValue>>),
+    /// Fields in wire order
+    Struct(Vec<(String, DynamicValue)>),
+    Enum {
+        variant: String,
+        fields: Vec<(String, DynamicValue)>,
+    },
+}
+impl DynamicValue {
+    /// Field of a struct or enum variant
+    pub fn field(&self, name: &str) -> Option<&DynamicValue> {
+        match self {
+            Self::Struct(fields) | Self::Enum { fields, .. } => {
+                fields.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Telemetry definition generated by `telemetry_definition`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DefinitionSchema {
+    pub id: u16,
+    pub address: String,
+    pub schema: TypeSchema,
+    #[serde(default)]
+    pub calibrations: Vec<CalibrationSchema>,
+}
+impl DefinitionSchema {
+    pub fn new(id: u16, address: &str, schema: TypeSchema) -> Self {
+        Self {
+            id,
+            address: address.into(),
+            schema,
+            calibrations: Vec::new(),
+        }
+    }
+    pub fn with_calibration(mut self, name: &str, function: &str) -> Self {
+        self.calibrations.push(CalibrationSchema {
+            name: name.into(),
+            function: function.into(),
+        });
+        self
+    }
+    fn decode(&self, bytes: &[u8], pos: usize) -> Result<(usize, DynamicValue), DecodeError> {
+        self.schema
+            .decode(bytes.get(pos..).unwrap_or(&[]))
+            .map_err(|error| DecodeError::InvalidValue {
+                address: self.address.clone(),
+                offset: pos,
+                error,
+            })
+    }
+}
+
+/// Ground calibration `#[tmv(.., name = function)]`, published under `address.name`.
+/// The function is rust source and can not be evaluated from the schema
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CalibrationSchema {
+    pub name: String,
+    pub function: String,
+}
+
+/// All definitions of a telemetry tree
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TelemetrySchema {
+    pub definitions: Vec<DefinitionSchema>,
+}
+impl TelemetrySchema {
+    pub fn new(definitions: impl Into<Vec<DefinitionSchema>>) -> Self {
+        Self {
+            definitions: definitions.into(),
+        }
+    }
+    pub fn from_id(&self, id: u16) -> Option<&DefinitionSchema> {
+        self.definitions.iter().find(|d| d.id == id)
+    }
+    pub fn from_address(&self, address: &str) -> Option<&DefinitionSchema> {
+        self.definitions.iter().find(|d| d.address == address)
+    }
+    /// Decodes the bytes of a [`crate::TelemetryContainer`] sent with `id`
+    pub fn decode_container(&self, id: u16, bytes: &[u8]) -> Result<DecodedTelemetry, DecodeError> {
+        let definition = self.from_id(id).ok_or(DecodeError::UnknownId(id))?;
+        let (_, value) = definition.decode(bytes, 0)?;
+        Ok(DecodedTelemetry::new(definition, value))
+    }
+}
+
+/// Frame layout of a beacon generated by `beacon!`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BeaconSchema {
+    pub name: String,
+    pub id: u8,
+    pub crc: CrcKind,
+    pub timestamp: DefinitionSchema,
+    /// Values in frame order, the n-th bit of the bitfield marks the n-th value as present
+    pub values: Vec<DefinitionSchema>,
+    /// Folded dictionary hash carried after the crc, if the beacon was built with one
+    #[serde(default)]
+    pub dictionary_hash: Option<u32>,
+}
+impl BeaconSchema {
+    pub fn new(
+        name: &str,
+        id: u8,
+        crc: CrcKind,
+        timestamp: DefinitionSchema,
+        values: impl Into<Vec<DefinitionSchema>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            id,
+            crc,
+            timestamp,
+            values: values.into(),
+            dictionary_hash: None,
+        }
+    }
+    pub fn with_dictionary_hash(mut self, hash: u32) -> Self {
+        self.dictionary_hash = Some(hash);
+        self
+    }
+    /// Decodes a frame written by [`crate::Beacon::to_bytes`]
+    pub fn decode(&self, bytes: &[u8]) -> Result<DecodedBeacon, DecodeError> {
+        let crc_end = 1 + self.crc.size();
+        let bitfield_start = crc_end + self.dictionary_hash.map_or(0, |_| 4);
+        let header_size = bitfield_start + self.values.len().div_ceil(8);
+        if bytes.len() < header_size {
+            return Err(DecodeError::OutOfMemory {
+                expected: header_size,
+                available: bytes.len(),
+            });
+        }
+        if bytes[0] != self.id {
+            return Err(DecodeError::WrongId {
+                expected: self.id as u16,
+                found: bytes[0] as u16,
+            });
+        }
+        let received = self.crc.read(&bytes[1..crc_end]);
+        let calculated = self.crc.checksum(&bytes[crc_end..]);
+        if received != calculated {
+            return Err(DecodeError::BadCRC {
+                received,
+                calculated,
+            });
+        }
+        if let Some(expected) = self.dictionary_hash {
+            let found = u32::from_le_bytes(bytes[crc_end..bitfield_start].try_into().unwrap());
+            if found != expected {
+                return Err(DecodeError::DictionaryMismatch { expected, found });
+            }
+        }
+        let bitfield = &bytes[bitfield_start..header_size];
+        let (len, timestamp) = self.timestamp.decode(bytes, header_size)?;
+        let mut pos = header_size + len;
+        let mut values = Vec::new();
+        for (i, definition) in self.values.iter().enumerate() {
+            if bitfield[i / 8] >> (i % 8) & 1 == 0 {
+                continue;
+            }
+            let (len, value) = definition.decode(bytes, pos)?;
+            pos += len;
+            values.push(DecodedTelemetry::new(definition, value));
+        }
+        Ok(DecodedBeacon { timestamp, values })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DecodedTelemetry {
+    pub id: u16,
+    pub address: String,
+    pub value: DynamicValue,
+}
+impl DecodedTelemetry {
+    fn new(definition: &DefinitionSchema, value: DynamicValue) -> Self {
+        Self {
+            id: definition.id,
+            address: definition.address.clone(),
+            value,
+        }
+    }
+}
+
+/// Present values of a beacon frame
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DecodedBeacon {
+    pub timestamp: DynamicValue,
+    pub values: Vec<DecodedTelemetry>,
+}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    UnknownId(u16),
+    WrongId {
+        expected: u16,
+        found: u16,
+    },
+    BadCRC {
+        received: u32,
+        calculated: u32,
+    },
+    DictionaryMismatch {
+        expected: u32,
+        found: u32,
+    },
+    /// The frame is too short for its header
+    OutOfMemory {
+        expected: usize,
+        available: usize,
+    },
+    InvalidValue {
+        address: String,
+        offset: usize,
+        error: TMValueError,
+    },
+}
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownId(id) => write!(f, "unknown telemetry id {}", id),
+            Self::WrongId { expected, found } => {
+                write!(f, "wrong id: expected {}, found {}", expected, found)
+            }
+            Self::BadCRC {
+                received,
+                calculated,
+            } => write!(
+                f,
+                "bad crc: received {:#x}, calculated {:#x}",
+                received, calculated
+            ),
+            Self::DictionaryMismatch { expected, found } => write!(
+                f,
+                "dictionary mismatch: expected hash {:#010x}, found {:#010x}",
+                expected, found
+            ),
+            Self::OutOfMemory {
+                expected,
+                available,
+            } => write!(
+                f,
+                "frame too short: expected {} header bytes, {} available",
+                expected, available
+            ),
+            Self::InvalidValue {
+                address,
+                offset,
+                error,
+            } => write!(
+                f,
+                "could not decode {} at byte offset {}: {}",
+                address, offset, error
+            ),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{Beacon, BeaconOperationError, SpacePacketError, TMValue, TelemetryContainer};
+
+/// Size of the CCSDS space packet primary header
+pub const PRIMARY_HEADER_SIZE: usize = 6;
+/// Largest application process identifier fitting into 11 bits
+pub const MAX_APID: u16 = 0x7FF;
+const SEQUENCE_COUNT_MASK: u16 = 0x3FFF;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PacketType {
+    Telemetry = 0,
+    Telecommand = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SequenceFlags {
+    Continuation = 0,
+    First = 1,
+    Last = 2,
+    Unsegmented = 3,
+}
+
+/// CCSDS space packet primary header (CCSDS 133.0-B-2), big endian on the wire
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrimaryHeader {
+    pub packet_type: PacketType,
+    pub secondary_header: bool,
+    pub apid: u16,
+    pub sequence_flags: SequenceFlags,
+    pub sequence_count: u16,
+    /// Length of the packet data field minus one
+    pub data_length: u16,
+}
+impl PrimaryHeader {
+    pub fn read(bytes: &[u8]) -> Result<Self, SpacePacketError> {
+        let header: [u8; PRIMARY_HEADER_SIZE] = bytes
+            .get(..PRIMARY_HEADER_SIZE)
+            .ok_or(SpacePacketError::OutOfMemory)?
+            .try_into()
+            .unwrap();
+        let identification = u16::from_be_bytes([header[0], header[1]]);
+        let sequence_control = u16::from_be_bytes([header[2], header[3]]);
+        if identification >> 13 != 0 {
+            return Err(SpacePacketError::UnsupportedVersion);
+        }
+        let packet_type = if identification & 0x1000 == 0 {
+            PacketType::Telemetry
+        } else {
+            PacketType::Telecommand
+        };
+        let sequence_flags = match sequence_control >> 14 {
+            0 => SequenceFlags::Continuation,
+            1 => SequenceFlags::First,
+            2 => SequenceFlags::Last,
+            _ => SequenceFlags::Unsegmented,
+        };
+        Ok(Self {
+            packet_type,
+            secondary_header: identification & 0x0800 != 0,
+            apid: identification & MAX_APID,
+            sequence_flags,
+            sequence_count: sequence_control & SEQUENCE_COUNT_MASK,
+            data_length: u16::from_be_bytes([header[4], header[5]]),
+        })
+    }
+    pub fn write(&self, mem: &mut [u8]) -> Result<usize, SpacePacketError> {
+        if self.apid > MAX_APID {
+            return Err(SpacePacketError::InvalidApid);
+        }
+        let header = mem
+            .get_mut(..PRIMARY_HEADER_SIZE)
+            .ok_or(SpacePacketError::OutOfMemory)?;
+        let identification =
+            ((self.packet_type as u16) << 12) | ((self.secondary_header as u16) << 11) | self.apid;
+        let sequence_control =
+            ((self.sequence_flags as u16) << 14) | (self.sequence_count & SEQUENCE_COUNT_MASK);
+        header[0..2].copy_from_slice(&identification.to_be_bytes());
+        header[2..4].copy_from_slice(&sequence_control.to_be_bytes());
+        header[4..6].copy_from_slice(&self.data_length.to_be_bytes());
+        Ok(PRIMARY_HEADER_SIZE)
+    }
+    /// Length of the whole packet including the primary header
+    pub fn packet_len(&self) -> usize {
+        PRIMARY_HEADER_SIZE + self.data_length as usize + 1
+    }
+}
+
+/// Wraps beacons and telemetry containers into unsegmented telemetry space packets
+/// and keeps track of the sequence count of its APID
+pub struct SpacePacketEncoder {
+    apid: u16,
+    sequence_count: u16,
+}
+impl SpacePacketEncoder {
+    /// For telemetry containers the APID should be the root id of the
+    /// telemetry definition tree, e.g. `telemetry::id_range().0`
+    pub const fn new(apid: u16) -> Result<Self, SpacePacketError> {
+        if apid > MAX_APID {
+            return Err(SpacePacketError::InvalidApid);
+        }
+        Ok(Self {
+            apid,
+            sequence_count: 0,
+        })
+    }
+    /// Encoder using the beacon id as APID
+    pub fn for_beacon(beacon: &impl Beacon) -> Self {
+        Self {
+            apid: beacon.id() as u16,
+            sequence_count: 0,
+        }
+    }
+    pub fn apid(&self) -> u16 {
+        self.apid
+    }
+    /// Sequence count the next packet will be sent with
+    pub fn sequence_count(&self) -> u16 {
+        self.sequence_count
+    }
+    /// Encodes a packet whose data field consists of the optional secondary header followed by the user data
+    pub fn encode(
+        &mut self,
+        secondary_header: &[u8],
+        user_data: &[u8],
+        mem: &mut [u8],
+    ) -> Result<usize, SpacePacketError> {
+        let data_len = secondary_header.len() + user_data.len();
+        let data_field = mem
+            .get_mut(PRIMARY_HEADER_SIZE..PRIMARY_HEADER_SIZE + data_len)
+            .ok_or(SpacePacketError::OutOfMemory)?;
+        let (secondary, user) = data_field.split_at_mut(secondary_header.len());
+        secondary.copy_from_slice(secondary_header);
+        user.copy_from_slice(user_data);
+        self.encode_header(!secondary_header.is_empty(), data_len, mem)
+    }
+    /// Encodes the beacon as user data, optionally with its timestamp as secondary header
+    pub fn encode_beacon<B: Beacon>(
+        &mut self,
+        beacon: &mut B,
+        timestamp: bool,
+        mem: &mut [u8],
+    ) -> Result<usize, SpacePacketError> {
+        let mut pos = PRIMARY_HEADER_SIZE;
+        if timestamp {
+            pos += beacon
+                .timestamp()
+                .write(mem.get_mut(pos..).ok_or(SpacePacketError::OutOfMemory)?)?;
+        }
+        let bytes = beacon.to_bytes().map_err(|error| match error {
+            BeaconOperationError::InvalidValue { error, .. } => SpacePacketError::from(error),
+            _ => SpacePacketError::OutOfMemory,
+        })?;
+        let len = pos + bytes.len();
+        mem.get_mut(pos..len)
+            .ok_or(SpacePacketError::OutOfMemory)?
+            .copy_from_slice(bytes);
+        self.encode_header(timestamp, len - PRIMARY_HEADER_SIZE, mem)
+    }
+    /// Encodes the little endian definition id followed by the container bytes as user data
+    pub fn encode_container<const N: usize>(
+        &mut self,
+        container: &TelemetryContainer<N>,
+        mem: &mut [u8],
+    ) -> Result<usize, SpacePacketError> {
+        let pos = PRIMARY_HEADER_SIZE;
+        let bytes = container.bytes();
+        let len = pos + 2 + bytes.len();
+        let data_field = mem.get_mut(pos..len).ok_or(SpacePacketError::OutOfMemory)?;
+        data_field[..2].copy_from_slice(&container.id().to_le_bytes());
+        data_field[2..].copy_from_slice(bytes);
+        self.encode_header(false, len - PRIMARY_HEADER_SIZE, mem)
+    }
+    /// Writes the primary header in front of a packet data field of `data_len` bytes
+    /// that was already placed at `PRIMARY_HEADER_SIZE`. Returns the packet length
+    pub fn encode_header(
+        &mut self,
+        secondary_header: bool,
+        data_len: usize,
+        mem: &mut [u8],
+    ) -> Result<usize, SpacePacketError> {
+        if data_len == 0 || data_len > u16::MAX as usize + 1 {
+            return Err(SpacePacketError::InvalidLength);
+        }
+        PrimaryHeader {
+            packet_type: PacketType::Telemetry,
+            secondary_header,
+            apid: self.apid,
+            sequence_flags: SequenceFlags::Unsegmented,
+            sequence_count: self.sequence_count,
+            data_length: (data_len - 1) as u16,
+        }
+        .write(mem)?;
+        self.increment();
+        Ok(PRIMARY_HEADER_SIZE + data_len)
+    }
+    fn increment(&mut self) {
+        self.sequence_count = (self.sequence_count + 1) & SEQUENCE_COUNT_MASK;
+    }
+}
+
+/// A decoded space packet borrowing its data field from the received bytes
+pub struct SpacePacket<'a> {
+    pub header: PrimaryHeader,
+    /// Packet data field including the secondary header, if present
+    pub data: &'a [u8],
+}
+impl<'a> SpacePacket<'a> {
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, SpacePacketError> {
+        let header = PrimaryHeader::read(bytes)?;
+        let data = bytes
+            .get(PRIMARY_HEADER_SIZE..header.packet_len())
+            .ok_or(SpacePacketError::OutOfMemory)?;
+        Ok(Self { header, data })
+    }
+    /// Splits a beacon packet into the timestamp secondary header and the beacon bytes
+    pub fn split_timestamp<T: TMValue>(&self) -> Result<(T, &'a [u8]), SpacePacketError> {
+        if !self.header.secondary_header {
+            return Err(SpacePacketError::MissingSecondaryHeader);
+        }
+        let (len, timestamp) = T::read(self.data).map_err(|_| SpacePacketError::OutOfMemory)?;
+        Ok((timestamp, &self.data[len..]))
+    }
+    /// Splits a container packet into the definition id and the value bytes
+    pub fn split_container(&self) -> Result<(u16, &'a [u8]), SpacePacketError> {
+        let (len, id) = u16::read(self.data).map_err(|_| SpacePacketError::OutOfMemory)?;
+        Ok((id, &self.data[len..]))
+    }
+}
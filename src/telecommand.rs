@@ -0,0 +1,88 @@
+use crate::{
+    _internal::InternalTelecommandDefinition, DispatchError, ParseError, TMValue, TMValueError,
+};
+
+/// Header in front of every telecommand frame
+#[derive(TMValue, Debug, Clone, Copy, PartialEq)]
+pub struct TelecommandHeader {
+    pub id: u16,
+    /// Chosen by the sender and echoed in the verification reports of the command
+    pub sequence: u16,
+}
+
+/// Size of the little endian command id and sequence count in front of every telecommand frame
+pub const TELECOMMAND_HEADER_SIZE: usize = TelecommandHeader::MAX_BYTE_SIZE;
+
+/// Typed encoding and decoding of telecommand frames.
+/// A frame consists of the [`TelecommandHeader`] followed by the encoded arguments
+pub trait Telecommand: InternalTelecommandDefinition {
+    fn encode(
+        &self,
+        sequence: u16,
+        args: &Self::TCValueType,
+        mem: &mut [u8],
+    ) -> Result<usize, TMValueError>;
+    fn decode(&self, frame: &[u8]) -> Result<Self::TCValueType, ParseError>;
+}
+
+impl<C: InternalTelecommandDefinition> Telecommand for C {
+    fn encode(
+        &self,
+        sequence: u16,
+        args: &Self::TCValueType,
+        mem: &mut [u8],
+    ) -> Result<usize, TMValueError> {
+        let header = TelecommandHeader {
+            id: Self::ID,
+            sequence,
+        };
+        let mut pos = header.write(mem)?;
+        pos += args.write(mem.get_mut(pos..).ok_or(TMValueError::OutOfMemory)?)?;
+        Ok(pos)
+    }
+    fn decode(&self, frame: &[u8]) -> Result<Self::TCValueType, ParseError> {
+        let (pos, header) =
+            TelecommandHeader::read(frame).map_err(|_| ParseError::OutOfMemory {
+                expected: TELECOMMAND_HEADER_SIZE,
+                available: frame.len(),
+            })?;
+        if header.id != Self::ID {
+            return Err(ParseError::WrongId {
+                expected: Self::ID,
+                found: header.id,
+            });
+        }
+        let (len, args) =
+            Self::TCValueType::read(&frame[pos..]).map_err(|error| ParseError::InvalidValue {
+                address: Self::ADDRESS,
+                offset: pos,
+                expected: Self::MAX_BYTE_SIZE,
+                available: frame.len() - pos,
+                error,
+            })?;
+        if pos + len != frame.len() {
+            return Err(ParseError::TrailingBytes {
+                expected: pos + len,
+                found: frame.len(),
+            });
+        }
+        Ok(args)
+    }
+}
+
+/// Handler for the telecommand `C`, called with the decoded arguments.
+/// Execution failures are reported with an application specific error code
+pub trait TelecommandHandler<C: InternalTelecommandDefinition> {
+    fn handle(&mut self, args: C::TCValueType) -> Result<(), u8>;
+}
+
+/// Dispatcher generated for every telecommand definition tree.
+/// Returns the header of the executed command. Errors come with the header
+/// if it could be read, to report the failure for the right command
+pub trait TelecommandDispatcher<H> {
+    fn dispatch(
+        &self,
+        frame: &[u8],
+        handler: &mut H,
+    ) -> Result<TelecommandHeader, (Option<TelecommandHeader>, DispatchError)>;
+}
@@ -2,15 +2,57 @@
 pub enum TMValueError {
     OutOfMemory,
     BadEnumVariant,
+    BadValue,
+    /// A `#[tm(const = ..)]` field did not hold its fixed value
+    BadConstant,
+    /// A length prefix exceeds the capacity of the bounded type
+    TooLong,
+    InvalidUtf8,
+}
+impl core::fmt::Display for TMValueError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfMemory => write!(f, "out of memory"),
+            Self::BadEnumVariant => write!(f, "bad enum variant"),
+            Self::BadValue => write!(f, "bad value"),
+            Self::BadConstant => write!(f, "bad constant"),
+            Self::TooLong => write!(f, "length exceeds capacity"),
+            Self::InvalidUtf8 => write!(f, "invalid utf-8"),
+        }
+    }
 }
 
 // # Trait definitions
 pub trait TMValue {
     const MAX_BYTE_SIZE: usize;
+    /// Hash of the wire layout, types without a layout of their own only hash their size
+    const LAYOUT_HASH: u64 = layout_hash("Opaque", &[Self::MAX_BYTE_SIZE as u64]);
     fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError>
     where
         Self: Sized;
     fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError>;
+    /// Big endian counterpart of [`TMValue::read`], types without
+    /// a byte order of their own fall back to their default encoding
+    fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError>
+    where
+        Self: Sized,
+    {
+        Self::read(bytes)
+    }
+    /// Big endian counterpart of [`TMValue::write`]
+    fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        self.write(mem)
+    }
+    /// Layout of the encoding for ground tools, raw bytes unless overridden
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema
+    where
+        Self: Sized,
+    {
+        TypeSchema::Opaque {
+            size: Self::MAX_BYTE_SIZE,
+        }
+    }
 }
 
 #[cfg(feature = "ground")]
@@ -30,7 +72,7 @@ pub mod ground_tm {
         DEF: TelemetryDefinition,
     {
         fn serialize_ground<T, S>(
-            self,
+            &self,
             _def: &DEF,
             timestamp: T,
             serializer: &S,
@@ -39,23 +81,79 @@ pub mod ground_tm {
             T: serde::Serialize + Clone + Copy,
             S: Serializer;
     }
-    #[derive(serde::Serialize)]
-    pub struct GroundTelemetry<T: serde::Serialize, V: serde::Serialize> {
+    // generic ground deserializer function wrapper
+    pub trait Deserializer {
+        type Error: Debug;
+        fn deserialize_value<V: serde::de::DeserializeOwned>(
+            &self,
+            bytes: &[u8],
+        ) -> Result<V, Self::Error>;
+    }
+    pub trait DeserializableTMValue<DEF>: super::TMValue + serde::de::DeserializeOwned
+    where
+        DEF: TelemetryDefinition,
+    {
+        /// Reads back the raw value [`SerializableTMValue::serialize_ground`]
+        /// stored under the address of the definition
+        fn deserialize_ground<T, D>(
+            _def: &DEF,
+            bytes: &[u8],
+            deserializer: &D,
+        ) -> Result<GroundTelemetry<T, Self>, D::Error>
+        where
+            T: serde::de::DeserializeOwned,
+            D: Deserializer;
+    }
+    /// Error of rebuilding a beacon from its serialized values
+    #[derive(Debug)]
+    pub enum BeaconDeserializeError<E> {
+        Deserialize(E),
+        /// The values were taken at different times, they do not belong to one beacon
+        TimestampMismatch,
+    }
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct GroundTelemetry<T, V> {
         timestamp: T,
         value: V,
     }
-    impl<T: serde::Serialize, V: serde::Serialize> GroundTelemetry<T, V> {
+    impl<T, V> GroundTelemetry<T, V> {
         pub fn new(timestamp: T, value: V) -> Self {
             Self { timestamp, value }
         }
+        pub fn timestamp(&self) -> &T {
+            &self.timestamp
+        }
+        pub fn value(&self) -> &V {
+            &self.value
+        }
+        pub fn into_parts(self) -> (T, V) {
+            (self.timestamp, self.value)
+        }
     }
 }
 
+use crate::layout_hash::layout_hash;
+#[cfg(feature = "ground")]
+use crate::schema::{Primitive, TypeSchema};
+#[cfg(feature = "ground")]
+use alloc::boxed::Box;
+
+// read and write of an element type, picks the byte order of containers
+type ReadFn<T> = fn(&[u8]) -> Result<(usize, T), TMValueError>;
+type WriteFn<T> = fn(&T, &mut [u8]) -> Result<usize, TMValueError>;
+
 // # Primitives
 macro_rules! primitive_value {
-    ($type:ident) => {
+    ($type:ident, $kind:ident) => {
         impl TMValue for $type {
             const MAX_BYTE_SIZE: usize = size_of::<Self>();
+            const LAYOUT_HASH: u64 = layout_hash(stringify!($kind), &[size_of::<Self>() as u64]);
+            #[cfg(feature = "ground")]
+            fn schema() -> TypeSchema {
+                TypeSchema::Primitive(Primitive::$kind {
+                    bytes: size_of::<Self>() as u8,
+                })
+            }
             fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
                 if bytes.len() < Self::MAX_BYTE_SIZE {
                     return Err(TMValueError::OutOfMemory);
@@ -71,99 +169,429 @@ macro_rules! primitive_value {
                 mem[..Self::MAX_BYTE_SIZE].copy_from_slice(&bytes);
                 Ok(Self::MAX_BYTE_SIZE)
             }
+            fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+                if bytes.len() < Self::MAX_BYTE_SIZE {
+                    return Err(TMValueError::OutOfMemory);
+                }
+                let value = Self::from_be_bytes(bytes[..Self::MAX_BYTE_SIZE].try_into().unwrap());
+                Ok((Self::MAX_BYTE_SIZE, value))
+            }
+            fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+                if mem.len() < Self::MAX_BYTE_SIZE {
+                    return Err(TMValueError::OutOfMemory);
+                }
+                let bytes = self.to_be_bytes();
+                mem[..Self::MAX_BYTE_SIZE].copy_from_slice(&bytes);
+                Ok(Self::MAX_BYTE_SIZE)
+            }
         }
     };
 }
 
-primitive_value!(u8);
-primitive_value!(u16);
-primitive_value!(u32);
-primitive_value!(u64);
-primitive_value!(u128);
-primitive_value!(usize);
+primitive_value!(u8, Unsigned);
+primitive_value!(u16, Unsigned);
+primitive_value!(u32, Unsigned);
+primitive_value!(u64, Unsigned);
+primitive_value!(u128, Unsigned);
+primitive_value!(usize, Unsigned);
+
+primitive_value!(i8, Signed);
+primitive_value!(i16, Signed);
+primitive_value!(i32, Signed);
+primitive_value!(i64, Signed);
+primitive_value!(i128, Signed);
+primitive_value!(isize, Signed);
+
+primitive_value!(f32, Float);
+primitive_value!(f64, Float);
+
+impl TMValue for bool {
+    const MAX_BYTE_SIZE: usize = 1;
+    const LAYOUT_HASH: u64 = layout_hash("Bool", &[]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::Primitive(Primitive::Bool)
+    }
+    fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        match bytes.first().ok_or(TMValueError::OutOfMemory)? {
+            0u8 => Ok((1, false)),
+            1u8 => Ok((1, true)),
+            _ => Err(TMValueError::BadValue),
+        }
+    }
+    fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        *mem.first_mut().ok_or(TMValueError::OutOfMemory)? = *self as u8;
+        Ok(1)
+    }
+}
 
-primitive_value!(i8);
-primitive_value!(i16);
-primitive_value!(i32);
-primitive_value!(i64);
-primitive_value!(i128);
-primitive_value!(isize);
+// chars are sent as their unicode scalar value
+impl TMValue for char {
+    const MAX_BYTE_SIZE: usize = size_of::<u32>();
+    const LAYOUT_HASH: u64 = layout_hash("Char", &[]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::Primitive(Primitive::Char)
+    }
+    fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        let (len, value) = u32::read(bytes)?;
+        Ok((len, char::from_u32(value).ok_or(TMValueError::BadValue)?))
+    }
+    fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        (*self as u32).write(mem)
+    }
+    fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        let (len, value) = u32::read_be(bytes)?;
+        Ok((len, char::from_u32(value).ok_or(TMValueError::BadValue)?))
+    }
+    fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        (*self as u32).write_be(mem)
+    }
+}
 
-primitive_value!(f32);
-primitive_value!(f64);
+// # Zero sized types
+impl TMValue for () {
+    const MAX_BYTE_SIZE: usize = 0;
+    const LAYOUT_HASH: u64 = layout_hash("Unit", &[]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::Unit
+    }
+    fn read(_bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        Ok((0, ()))
+    }
+    fn write(&self, _mem: &mut [u8]) -> Result<usize, TMValueError> {
+        Ok(0)
+    }
+}
+impl<T: ?Sized> TMValue for core::marker::PhantomData<T> {
+    const MAX_BYTE_SIZE: usize = 0;
+    const LAYOUT_HASH: u64 = layout_hash("Unit", &[]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::Unit
+    }
+    fn read(_bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        Ok((0, core::marker::PhantomData))
+    }
+    fn write(&self, _mem: &mut [u8]) -> Result<usize, TMValueError> {
+        Ok(0)
+    }
+}
 
 // # Arrays
 impl<const N: usize, T: TMValue> TMValue for [T; N] {
     const MAX_BYTE_SIZE: usize = N * T::MAX_BYTE_SIZE;
-    fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
-        unsafe {
-            let mut pos = 0;
-            let mut arr: Self = core::mem::zeroed();
-            for i in 0..N {
-                let (len, value) = T::read(&bytes[pos..])?;
-                pos += len;
-                arr[i] = value;
-            }
-            Ok((pos, arr))
+    const LAYOUT_HASH: u64 = layout_hash("Array", &[N as u64, T::LAYOUT_HASH]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::Array {
+            len: N,
+            element: Box::new(T::schema()),
         }
     }
+    fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        read_array(bytes, T::read)
+    }
     fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
-        let mut pos = 0;
-        for i in 0..N {
-            pos += self[i].write(&mut mem[pos..])?;
+        write_array(self, mem, T::write)
+    }
+    fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        read_array(bytes, T::read_be)
+    }
+    fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        write_array(self, mem, T::write_be)
+    }
+}
+fn read_array<const N: usize, T>(
+    bytes: &[u8],
+    read: ReadFn<T>,
+) -> Result<(usize, [T; N]), TMValueError> {
+    let mut pos = 0;
+    let arr = core::array::try_from_fn(|_| {
+        let (len, value) = read(bytes.get(pos..).ok_or(TMValueError::OutOfMemory)?)?;
+        pos += len;
+        Ok(value)
+    })?;
+    Ok((pos, arr))
+}
+fn write_array<T>(values: &[T], mem: &mut [u8], write: WriteFn<T>) -> Result<usize, TMValueError> {
+    let mut pos = 0;
+    for value in values {
+        pos += write(value, mem.get_mut(pos..).ok_or(TMValueError::OutOfMemory)?)?;
+    }
+    Ok(pos)
+}
+
+// # Bounded sequences
+#[cfg(feature = "heapless")]
+mod bounded {
+    use super::{ReadFn, TMValue, TMValueError, WriteFn};
+    use crate::layout_hash::layout_hash;
+    #[cfg(feature = "ground")]
+    use crate::schema::TypeSchema;
+    #[cfg(feature = "ground")]
+    use alloc::boxed::Box;
+
+    /// Width of the little endian length prefix of a sequence holding up to `capacity` elements
+    const fn length_prefix_size(capacity: usize) -> usize {
+        if capacity <= u8::MAX as usize {
+            1
+        } else if capacity <= u16::MAX as usize {
+            2
+        } else {
+            4
+        }
+    }
+    fn read_length(
+        bytes: &[u8],
+        capacity: usize,
+        big_endian: bool,
+    ) -> Result<(usize, usize), TMValueError> {
+        fn widen<T: Into<u32>>((pos, len): (usize, T)) -> (usize, usize) {
+            (pos, len.into() as usize)
+        }
+        let (pos, len) = match (length_prefix_size(capacity), big_endian) {
+            (1, _) => widen(u8::read(bytes)?),
+            (2, false) => widen(u16::read(bytes)?),
+            (2, true) => widen(u16::read_be(bytes)?),
+            (_, false) => widen(u32::read(bytes)?),
+            (_, true) => widen(u32::read_be(bytes)?),
+        };
+        if len > capacity {
+            return Err(TMValueError::TooLong);
+        }
+        Ok((pos, len))
+    }
+    fn write_length(
+        len: usize,
+        capacity: usize,
+        big_endian: bool,
+        mem: &mut [u8],
+    ) -> Result<usize, TMValueError> {
+        match (length_prefix_size(capacity), big_endian) {
+            (1, _) => (len as u8).write(mem),
+            (2, false) => (len as u16).write(mem),
+            (2, true) => (len as u16).write_be(mem),
+            (_, false) => (len as u32).write(mem),
+            (_, true) => (len as u32).write_be(mem),
+        }
+    }
+
+    fn read_vec<const N: usize, T>(
+        bytes: &[u8],
+        big_endian: bool,
+        read: ReadFn<T>,
+    ) -> Result<(usize, heapless::Vec<T, N>), TMValueError> {
+        let (mut pos, len) = read_length(bytes, N, big_endian)?;
+        let mut vec = heapless::Vec::new();
+        for _ in 0..len {
+            let (len, value) = read(bytes.get(pos..).ok_or(TMValueError::OutOfMemory)?)?;
+            // cannot fail, the length was checked against the capacity
+            let _ = vec.push(value);
+            pos += len;
+        }
+        Ok((pos, vec))
+    }
+    fn write_vec<const N: usize, T>(
+        vec: &heapless::Vec<T, N>,
+        mem: &mut [u8],
+        big_endian: bool,
+        write: WriteFn<T>,
+    ) -> Result<usize, TMValueError> {
+        let mut pos = write_length(vec.len(), N, big_endian, mem)?;
+        for value in vec {
+            pos += write(value, mem.get_mut(pos..).ok_or(TMValueError::OutOfMemory)?)?;
         }
         Ok(pos)
     }
+
+    impl<const N: usize, T: TMValue> TMValue for heapless::Vec<T, N> {
+        const MAX_BYTE_SIZE: usize = length_prefix_size(N) + N * T::MAX_BYTE_SIZE;
+        const LAYOUT_HASH: u64 = layout_hash("Vec", &[N as u64, T::LAYOUT_HASH]);
+        #[cfg(feature = "ground")]
+        fn schema() -> TypeSchema {
+            TypeSchema::Vec {
+                capacity: N,
+                length_bytes: length_prefix_size(N) as u8,
+                element: Box::new(T::schema()),
+            }
+        }
+        fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+            read_vec(bytes, false, T::read)
+        }
+        fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+            write_vec(self, mem, false, T::write)
+        }
+        fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+            read_vec(bytes, true, T::read_be)
+        }
+        fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+            write_vec(self, mem, true, T::write_be)
+        }
+    }
+
+    // strings are prefixed with their length in bytes
+    fn read_string<const N: usize>(
+        bytes: &[u8],
+        big_endian: bool,
+    ) -> Result<(usize, heapless::String<N>), TMValueError> {
+        let (pos, len) = read_length(bytes, N, big_endian)?;
+        let utf8 = bytes.get(pos..pos + len).ok_or(TMValueError::OutOfMemory)?;
+        let string = core::str::from_utf8(utf8).map_err(|_| TMValueError::InvalidUtf8)?;
+        // cannot fail, the length was checked against the capacity
+        Ok((pos + len, heapless::String::try_from(string).unwrap()))
+    }
+    fn write_string<const N: usize>(
+        string: &heapless::String<N>,
+        mem: &mut [u8],
+        big_endian: bool,
+    ) -> Result<usize, TMValueError> {
+        let pos = write_length(string.len(), N, big_endian, mem)?;
+        mem.get_mut(pos..pos + string.len())
+            .ok_or(TMValueError::OutOfMemory)?
+            .copy_from_slice(string.as_bytes());
+        Ok(pos + string.len())
+    }
+
+    impl<const N: usize> TMValue for heapless::String<N> {
+        const MAX_BYTE_SIZE: usize = length_prefix_size(N) + N;
+        const LAYOUT_HASH: u64 = layout_hash("String", &[N as u64]);
+        #[cfg(feature = "ground")]
+        fn schema() -> TypeSchema {
+            TypeSchema::String {
+                capacity: N,
+                length_bytes: length_prefix_size(N) as u8,
+            }
+        }
+        fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+            read_string(bytes, false)
+        }
+        fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+            write_string(self, mem, false)
+        }
+        fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+            read_string(bytes, true)
+        }
+        fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+            write_string(self, mem, true)
+        }
+    }
 }
-// # Vectors
-// use heapless::Vec;
-// impl<const N: usize, T: TMValue> TMValue for Vec<T, N> {
-//     const BYTE_SIZE: usize = N * T::BYTE_SIZE;
-//     fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
-//         let (mut pos, len) = u8::read(bytes)?;
-//         let mut vec = Vec::new();
-//         for _ in 0..len {
-//             let (len, value) = T::read(&bytes[pos..])?;
-//             vec.push(value);
-//             pos += len;
-//         }
-//         Ok((pos, vec))
-//     }
-//     fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
-//         let mut pos = (self.len() as u8).write(mem)?;
-//         for i in 0..self.len() {
-//             pos += self[i].write(&mut mem[pos..])?;
-//         }
-//         Ok(pos)
-//     }
-// }
 
 // # Options
 impl<T: TMValue> TMValue for Option<T> {
     const MAX_BYTE_SIZE: usize = 1 + T::MAX_BYTE_SIZE;
+    const LAYOUT_HASH: u64 = layout_hash("Option", &[T::LAYOUT_HASH]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::Option(Box::new(T::schema()))
+    }
     fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
-        let mut pos = 1;
-        match bytes[0] {
-            0u8 => Ok((pos, None)),
-            1u8 => {
-                let (len, value) = T::read(&bytes[pos..])?;
-                pos += len;
-                Ok((pos, Some(value)))
-            }
-            _ => Err(TMValueError::BadEnumVariant),
+        read_option(bytes, T::read)
+    }
+    fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        write_option(self, mem, T::write)
+    }
+    fn read_be(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        read_option(bytes, T::read_be)
+    }
+    fn write_be(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        write_option(self, mem, T::write_be)
+    }
+}
+fn read_option<T>(bytes: &[u8], read: ReadFn<T>) -> Result<(usize, Option<T>), TMValueError> {
+    let mut pos = 1;
+    match bytes.first().ok_or(TMValueError::OutOfMemory)? {
+        0u8 => Ok((pos, None)),
+        1u8 => {
+            let (len, value) = read(&bytes[pos..])?;
+            pos += len;
+            Ok((pos, Some(value)))
+        }
+        _ => Err(TMValueError::BadEnumVariant),
+    }
+}
+fn write_option<T>(
+    value: &Option<T>,
+    mem: &mut [u8],
+    write: WriteFn<T>,
+) -> Result<usize, TMValueError> {
+    let mut pos = 1;
+    let tag = mem.first_mut().ok_or(TMValueError::OutOfMemory)?;
+    match value {
+        None => {
+            *tag = 0u8;
+        }
+        Some(v0) => {
+            *tag = 1u8;
+            pos += write(v0, &mut mem[pos..])?;
+        }
+    }
+    Ok(pos)
+}
+
+// # Byte order
+/// Encodes the wrapped value big endian, whatever the surrounding type uses
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "ground",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct BigEndian<T>(pub T);
+impl<T: TMValue> TMValue for BigEndian<T> {
+    const MAX_BYTE_SIZE: usize = T::MAX_BYTE_SIZE;
+    const LAYOUT_HASH: u64 = layout_hash("BigEndian", &[T::LAYOUT_HASH]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::ByteOrder {
+            big_endian: true,
+            value: Box::new(T::schema()),
         }
     }
+    fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        let (len, value) = T::read_be(bytes)?;
+        Ok((len, Self(value)))
+    }
     fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
-        let mut pos = 1;
-        match self {
-            None => {
-                mem[0] = 0u8;
-            }
-            Some(v0) => {
-                mem[0] = 1u8;
-                pos += v0.write(&mut mem[pos..])?;
-            }
+        self.0.write_be(mem)
+    }
+}
+impl<T> From<T> for BigEndian<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// Encodes the wrapped value little endian, whatever the surrounding type uses
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "ground",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct LittleEndian<T>(pub T);
+impl<T: TMValue> TMValue for LittleEndian<T> {
+    const MAX_BYTE_SIZE: usize = T::MAX_BYTE_SIZE;
+    const LAYOUT_HASH: u64 = layout_hash("LittleEndian", &[T::LAYOUT_HASH]);
+    #[cfg(feature = "ground")]
+    fn schema() -> TypeSchema {
+        TypeSchema::ByteOrder {
+            big_endian: false,
+            value: Box::new(T::schema()),
         }
-        Ok(pos)
+    }
+    fn read(bytes: &[u8]) -> Result<(usize, Self), TMValueError> {
+        let (len, value) = T::read(bytes)?;
+        Ok((len, Self(value)))
+    }
+    fn write(&self, mem: &mut [u8]) -> Result<usize, TMValueError> {
+        self.0.write(mem)
+    }
+}
+impl<T> From<T> for LittleEndian<T> {
+    fn from(value: T) -> Self {
+        Self(value)
     }
 }
@@ -0,0 +1,310 @@
+use crate::space_packet::{
+    MAX_APID, PRIMARY_HEADER_SIZE, PacketType, PrimaryHeader, SequenceFlags,
+};
+use crate::{TransferFrameError, crc};
+
+/// Size of the TM transfer frame primary header
+pub const FRAME_HEADER_SIZE: usize = 6;
+/// Size of the optional frame error control field
+pub const FECF_SIZE: usize = 2;
+/// Largest spacecraft id fitting into 10 bits
+pub const MAX_SPACECRAFT_ID: u16 = 0x3FF;
+/// Number of virtual channels addressable by the 3 bit VCID
+pub const MAX_VIRTUAL_CHANNELS: usize = 8;
+/// First header pointer of a frame in which no packet starts
+pub const NO_PACKET_START: u16 = 0x7FF;
+/// First header pointer of a frame only containing idle data
+pub const ONLY_IDLE_DATA: u16 = 0x7FE;
+
+/// Primary header of a CCSDS TM transfer frame (CCSDS 132.0-B-3) in packet mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameHeader {
+    pub spacecraft_id: u16,
+    pub virtual_channel: u8,
+    pub master_frame_count: u8,
+    pub virtual_frame_count: u8,
+    pub first_header_pointer: u16,
+}
+impl FrameHeader {
+    pub fn read(bytes: &[u8]) -> Result<Self, TransferFrameError> {
+        let header = bytes
+            .get(..FRAME_HEADER_SIZE)
+            .ok_or(TransferFrameError::InvalidLength)?;
+        let identification = u16::from_be_bytes([header[0], header[1]]);
+        if identification >> 14 != 0 {
+            return Err(TransferFrameError::UnsupportedVersion);
+        }
+        let status = u16::from_be_bytes([header[4], header[5]]);
+        Ok(Self {
+            spacecraft_id: (identification >> 4) & MAX_SPACECRAFT_ID,
+            virtual_channel: ((identification >> 1) & 0x7) as u8,
+            master_frame_count: header[2],
+            virtual_frame_count: header[3],
+            first_header_pointer: status & NO_PACKET_START,
+        })
+    }
+    pub fn write(&self, mem: &mut [u8]) -> Result<usize, TransferFrameError> {
+        let header = mem
+            .get_mut(..FRAME_HEADER_SIZE)
+            .ok_or(TransferFrameError::InvalidLength)?;
+        let identification = (self.spacecraft_id << 4) | ((self.virtual_channel as u16) << 1);
+        // synchronous, in order packets with segment length id 0b11
+        let status = (0b11 << 11) | self.first_header_pointer;
+        header[0..2].copy_from_slice(&identification.to_be_bytes());
+        header[2] = self.master_frame_count;
+        header[3] = self.virtual_frame_count;
+        header[4..6].copy_from_slice(&status.to_be_bytes());
+        Ok(FRAME_HEADER_SIZE)
+    }
+}
+
+struct VirtualChannel<const FRAME_LEN: usize> {
+    frame: [u8; FRAME_LEN],
+    pos: usize,
+    first_header: Option<usize>,
+    frame_count: u8,
+}
+
+/// Multiplexes space packets of up to `VIRTUAL_CHANNELS` virtual channels into
+/// fixed length TM transfer frames of `FRAME_LEN` bytes
+pub struct TransferFrameBuilder<const FRAME_LEN: usize, const VIRTUAL_CHANNELS: usize> {
+    spacecraft_id: u16,
+    fecf: bool,
+    master_frame_count: u8,
+    channels: [VirtualChannel<FRAME_LEN>; VIRTUAL_CHANNELS],
+}
+impl<const FRAME_LEN: usize, const VIRTUAL_CHANNELS: usize>
+    TransferFrameBuilder<FRAME_LEN, VIRTUAL_CHANNELS>
+{
+    pub fn new(spacecraft_id: u16, fecf: bool) -> Result<Self, TransferFrameError> {
+        if spacecraft_id > MAX_SPACECRAFT_ID {
+            return Err(TransferFrameError::InvalidSpacecraftId);
+        }
+        if VIRTUAL_CHANNELS > MAX_VIRTUAL_CHANNELS {
+            return Err(TransferFrameError::InvalidVirtualChannel);
+        }
+        // the data field has to hold at least an idle packet
+        if FRAME_LEN < FRAME_HEADER_SIZE + fecf as usize * FECF_SIZE + PRIMARY_HEADER_SIZE + 1 {
+            return Err(TransferFrameError::InvalidLength);
+        }
+        Ok(Self {
+            spacecraft_id,
+            fecf,
+            master_frame_count: 0,
+            channels: core::array::from_fn(|_| VirtualChannel {
+                frame: [0u8; FRAME_LEN],
+                pos: FRAME_HEADER_SIZE,
+                first_header: None,
+                frame_count: 0,
+            }),
+        })
+    }
+    fn data_end(&self) -> usize {
+        FRAME_LEN - self.fecf as usize * FECF_SIZE
+    }
+    /// Appends a space packet to the virtual channel, emitting every frame that is completed
+    pub fn push_packet(
+        &mut self,
+        virtual_channel: u8,
+        packet: &[u8],
+        emit: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), TransferFrameError> {
+        if (virtual_channel as usize) >= VIRTUAL_CHANNELS {
+            return Err(TransferFrameError::InvalidVirtualChannel);
+        }
+        let header = PrimaryHeader::read(packet).map_err(|_| TransferFrameError::InvalidPacket)?;
+        if header.packet_len() != packet.len() {
+            return Err(TransferFrameError::InvalidPacket);
+        }
+        self.push_bytes(virtual_channel, packet, emit);
+        Ok(())
+    }
+    /// Completes the partially filled frame of the virtual channel with an idle packet
+    pub fn flush(
+        &mut self,
+        virtual_channel: u8,
+        emit: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), TransferFrameError> {
+        if (virtual_channel as usize) >= VIRTUAL_CHANNELS {
+            return Err(TransferFrameError::InvalidVirtualChannel);
+        }
+        let channel = &self.channels[virtual_channel as usize];
+        if channel.pos == FRAME_HEADER_SIZE {
+            return Ok(());
+        }
+        let remaining = self.data_end() - channel.pos;
+        // too little space left for an idle packet, let it span into the next frame
+        let idle_len = if remaining < PRIMARY_HEADER_SIZE + 1 {
+            remaining + self.data_end() - FRAME_HEADER_SIZE
+        } else {
+            remaining
+        };
+        let mut header = [0u8; PRIMARY_HEADER_SIZE];
+        PrimaryHeader {
+            packet_type: PacketType::Telemetry,
+            secondary_header: false,
+            apid: MAX_APID,
+            sequence_flags: SequenceFlags::Unsegmented,
+            sequence_count: 0,
+            data_length: (idle_len - PRIMARY_HEADER_SIZE - 1) as u16,
+        }
+        .write(&mut header)
+        .map_err(|_| TransferFrameError::InvalidLength)?;
+        self.push_bytes(virtual_channel, &header, emit);
+        let mut idle_data = idle_len - PRIMARY_HEADER_SIZE;
+        while idle_data > 0 {
+            let idle = [0x55u8; 16];
+            let len = idle_data.min(idle.len());
+            self.push_continuation(virtual_channel, &idle[..len], emit);
+            idle_data -= len;
+        }
+        Ok(())
+    }
+    fn push_bytes(&mut self, virtual_channel: u8, packet: &[u8], emit: &mut dyn FnMut(&[u8])) {
+        let channel = &mut self.channels[virtual_channel as usize];
+        if channel.first_header.is_none() {
+            channel.first_header = Some(channel.pos - FRAME_HEADER_SIZE);
+        }
+        self.push_continuation(virtual_channel, packet, emit);
+    }
+    fn push_continuation(
+        &mut self,
+        virtual_channel: u8,
+        mut bytes: &[u8],
+        emit: &mut dyn FnMut(&[u8]),
+    ) {
+        let data_end = self.data_end();
+        while !bytes.is_empty() {
+            let channel = &mut self.channels[virtual_channel as usize];
+            let len = bytes.len().min(data_end - channel.pos);
+            channel.frame[channel.pos..channel.pos + len].copy_from_slice(&bytes[..len]);
+            channel.pos += len;
+            bytes = &bytes[len..];
+            if channel.pos == data_end {
+                self.emit_frame(virtual_channel, emit);
+            }
+        }
+    }
+    fn emit_frame(&mut self, virtual_channel: u8, emit: &mut dyn FnMut(&[u8])) {
+        let channel = &mut self.channels[virtual_channel as usize];
+        FrameHeader {
+            spacecraft_id: self.spacecraft_id,
+            virtual_channel,
+            master_frame_count: self.master_frame_count,
+            virtual_frame_count: channel.frame_count,
+            first_header_pointer: channel
+                .first_header
+                .map(|p| p as u16)
+                .unwrap_or(NO_PACKET_START),
+        }
+        .write(&mut channel.frame)
+        .unwrap();
+        if self.fecf {
+            let crc = crc::ccitt16(&channel.frame[..FRAME_LEN - FECF_SIZE]);
+            channel.frame[FRAME_LEN - FECF_SIZE..].copy_from_slice(&crc.to_be_bytes());
+        }
+        emit(&channel.frame);
+        channel.frame_count = channel.frame_count.wrapping_add(1);
+        channel.pos = FRAME_HEADER_SIZE;
+        channel.first_header = None;
+        self.master_frame_count = self.master_frame_count.wrapping_add(1);
+    }
+}
+
+#[cfg(feature = "ground")]
+pub use demultiplexer::TransferFrameDemultiplexer;
+
+#[cfg(feature = "ground")]
+mod demultiplexer {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[derive(Default)]
+    struct ChannelState {
+        next_frame_count: Option<u8>,
+        synced: bool,
+        buffer: Vec<u8>,
+    }
+
+    /// Ground side counterpart of the [`TransferFrameBuilder`], reassembles
+    /// the space packets of every virtual channel from received frames
+    pub struct TransferFrameDemultiplexer {
+        frame_len: usize,
+        fecf: bool,
+        channels: [ChannelState; MAX_VIRTUAL_CHANNELS],
+    }
+    impl TransferFrameDemultiplexer {
+        pub fn new(frame_len: usize, fecf: bool) -> Result<Self, TransferFrameError> {
+            // same minimum as the builder, the data field holds at least an idle packet
+            if frame_len < FRAME_HEADER_SIZE + fecf as usize * FECF_SIZE + PRIMARY_HEADER_SIZE + 1 {
+                return Err(TransferFrameError::InvalidLength);
+            }
+            Ok(Self {
+                frame_len,
+                fecf,
+                channels: Default::default(),
+            })
+        }
+        /// Returns the packets completed by this frame, idle packets are dropped.
+        /// After a lost frame or a malformed packet header the channel resynchronizes
+        /// at the next first header pointer
+        pub fn push_frame(
+            &mut self,
+            frame: &[u8],
+        ) -> Result<(FrameHeader, Vec<Vec<u8>>), TransferFrameError> {
+            if frame.len() != self.frame_len {
+                return Err(TransferFrameError::InvalidLength);
+            }
+            let data_end = self.frame_len - self.fecf as usize * FECF_SIZE;
+            if self.fecf {
+                let received = u16::from_be_bytes([frame[data_end], frame[data_end + 1]]);
+                if crc::ccitt16(&frame[..data_end]) != received {
+                    return Err(TransferFrameError::BadCRC);
+                }
+            }
+            let header = FrameHeader::read(frame)?;
+            let data = &frame[FRAME_HEADER_SIZE..data_end];
+            let channel = &mut self.channels[header.virtual_channel as usize];
+
+            if channel.next_frame_count != Some(header.virtual_frame_count) {
+                channel.synced = false;
+                channel.buffer.clear();
+            }
+            channel.next_frame_count = Some(header.virtual_frame_count.wrapping_add(1));
+
+            let mut packets = Vec::new();
+            if header.first_header_pointer == ONLY_IDLE_DATA {
+                return Ok((header, packets));
+            }
+            if channel.synced {
+                channel.buffer.extend_from_slice(data);
+            } else if header.first_header_pointer != NO_PACKET_START {
+                let first_header = header.first_header_pointer as usize;
+                let start = data
+                    .get(first_header..)
+                    .ok_or(TransferFrameError::InvalidPacket)?;
+                channel.buffer.extend_from_slice(start);
+                channel.synced = true;
+            }
+
+            while channel.buffer.len() >= PRIMARY_HEADER_SIZE {
+                let Ok(packet_header) = PrimaryHeader::read(&channel.buffer) else {
+                    // keep the packets completed before the malformed one
+                    channel.synced = false;
+                    channel.buffer.clear();
+                    break;
+                };
+                let len = packet_header.packet_len();
+                if channel.buffer.len() < len {
+                    break;
+                }
+                let rest = channel.buffer.split_off(len);
+                let packet = core::mem::replace(&mut channel.buffer, rest);
+                if packet_header.apid != MAX_APID {
+                    packets.push(packet);
+                }
+            }
+            Ok((header, packets))
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{DispatchError, TMValue, TMValueError, TelecommandHeader};
+
+/// Reason a telecommand was rejected or failed during execution
+#[derive(TMValue, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize, serde::Deserialize))]
+pub enum FailureCode {
+    MalformedFrame,
+    UnknownCommand,
+    BadArguments,
+    ExecutionFailed(u8),
+}
+impl FailureCode {
+    /// Whether the command was rejected before its execution started
+    pub fn is_acceptance_failure(&self) -> bool {
+        !matches!(self, Self::ExecutionFailed(_))
+    }
+}
+impl From<&DispatchError> for FailureCode {
+    fn from(error: &DispatchError) -> Self {
+        match error {
+            DispatchError::OutOfMemory | DispatchError::TrailingBytes(_) => Self::MalformedFrame,
+            DispatchError::UnknownId(_) => Self::UnknownCommand,
+            DispatchError::BadArguments(_) => Self::BadArguments,
+            DispatchError::ExecutionFailed(code) => Self::ExecutionFailed(*code),
+        }
+    }
+}
+
+/// Successful acceptance, start or completion of a telecommand.
+/// The sequence count is the one the command was sent with
+#[derive(TMValue, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionReport {
+    pub command_id: u16,
+    pub sequence: u16,
+}
+impl ExecutionReport {
+    pub fn new(command_id: u16, sequence: u16) -> Self {
+        Self {
+            command_id,
+            sequence,
+        }
+    }
+    /// Report for the command with the given header
+    pub fn for_command(header: &TelecommandHeader) -> Self {
+        Self::new(header.id, header.sequence)
+    }
+}
+
+/// Progress of a long running telecommand
+#[derive(TMValue, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgressReport {
+    pub command_id: u16,
+    pub sequence: u16,
+    pub step: u8,
+}
+impl ProgressReport {
+    pub fn new(command_id: u16, sequence: u16, step: u8) -> Self {
+        Self {
+            command_id,
+            sequence,
+            step,
+        }
+    }
+}
+
+/// Failed acceptance, start or completion of a telecommand
+#[derive(TMValue, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize, serde::Deserialize))]
+pub struct FailureReport {
+    pub command_id: u16,
+    pub sequence: u16,
+    pub code: FailureCode,
+}
+impl FailureReport {
+    pub fn new(command_id: u16, sequence: u16, code: FailureCode) -> Self {
+        Self {
+            command_id,
+            sequence,
+            code,
+        }
+    }
+    /// Report for the command with the given header
+    pub fn for_command(header: &TelecommandHeader, code: FailureCode) -> Self {
+        Self::new(header.id, header.sequence, code)
+    }
+}
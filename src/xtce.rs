@@ -0,0 +1,545 @@
+//! XTCE 1.2 export of the telemetry dictionary for mission control tools.
+//! Modules of the definition tree become nested space systems, beacons become
+//! sequence containers of the root space system. Definitions of other trees sent
+//! by the beacons get a space system named after their root module.
+//!
+//! Bounded vectors, options and enums with fields have no static XTCE layout,
+//! they are exported as binary parameters of their maximum size. Calibrations
+//! are rust functions and are only listed as ancillary data of their parameter
+
+use crate::schema::{
+    BeaconSchema, BitOrder, DefinitionSchema, FieldSchema, Primitive, TelemetrySchema, TypeSchema,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+const XTCE_NAMESPACE: &str = "http://www.omg.org/spec/XTCE/20180204";
+
+/// Writes the `SpaceSystem` document of a telemetry tree and the beacons sending it
+pub fn space_system(telemetry: &TelemetrySchema, beacons: &[BeaconSchema]) -> String {
+    let root = telemetry
+        .definitions
+        .iter()
+        .chain(beacons.iter().map(|b| &b.timestamp))
+        .find_map(|d| d.address.split('.').next())
+        .unwrap_or("telemetry")
+        .to_string();
+    let mut types = TypeSet {
+        root: root.clone(),
+        known: Vec::new(),
+        xml: String::new(),
+    };
+
+    // every definition is a parameter of the space system of its module
+    let mut tree = SystemNode::new(&root);
+    for definition in &telemetry.definitions {
+        tree.insert(definition, &mut types);
+    }
+    let mut inserted: Vec<&str> = Vec::new();
+    for beacon in beacons {
+        for definition in core::iter::once(&beacon.timestamp).chain(&beacon.values) {
+            let address = definition.address.as_str();
+            if telemetry.from_address(address).is_none() && !inserted.contains(&address) {
+                inserted.push(address);
+                tree.insert(definition, &mut types);
+            }
+        }
+    }
+
+    // beacon header fields are parameters of the root space system
+    let mut containers = String::new();
+    for beacon in beacons {
+        beacon_container(beacon, &mut tree.parameters, &mut containers, &mut types);
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    tree.write(&mut xml, 0, Some((&types.xml, &containers)));
+    xml
+}
+
+/// Parameter types of the document, shared by all space systems
+struct TypeSet {
+    root: String,
+    /// Schema key and name of every written type
+    known: Vec<(String, String)>,
+    xml: String,
+}
+impl TypeSet {
+    /// Absolute reference of the parameter type of `schema`, writing it on first use
+    fn type_ref(&mut self, schema: &TypeSchema, big_endian: bool) -> String {
+        if let TypeSchema::ByteOrder { big_endian, value } = schema {
+            return self.type_ref(value, *big_endian);
+        }
+        let key = format!("{:?}/{}", schema, big_endian);
+        if let Some((_, name)) = self.known.iter().find(|(k, _)| *k == key) {
+            return format!("/{}/{}", self.root, name);
+        }
+        // generic types share their name, number the instances
+        let base = type_name(schema, big_endian);
+        let mut name = base.clone();
+        let mut i = 1;
+        while self.known.iter().any(|(_, n)| *n == name) {
+            i += 1;
+            name = format!("{}_{}", base, i);
+        }
+        self.known.push((key, name.clone()));
+        let xml = self.type_xml(&name, schema, big_endian);
+        self.xml.push_str(&xml);
+        format!("/{}/{}", self.root, name)
+    }
+    /// Reference of a packed field or header type given by its bits
+    fn bits_type_ref(&mut self, primitive: Primitive, bits: u32, order: BitOrder) -> String {
+        let (kind, signed) = match primitive {
+            Primitive::Bool => ("bool", false),
+            Primitive::Signed { .. } => ("i", true),
+            _ => ("u", false),
+        };
+        let lsb = if order == BitOrder::Lsb { "_lsb" } else { "" };
+        let name = format!("{}{}_bits{}", kind, bits, lsb);
+        if !self.known.iter().any(|(_, n)| *n == name) {
+            self.known.push((name.clone(), name.clone()));
+            let bit_order = if order == BitOrder::Lsb {
+                " bitOrder=\"leastSignificantBitFirst\""
+            } else {
+                ""
+            };
+            let encoding = format!(
+                "<IntegerDataEncoding sizeInBits=\"{}\" encoding=\"{}\"{}/>",
+                bits,
+                integer_encoding(signed),
+                bit_order
+            );
+            let xml = if primitive == Primitive::Bool {
+                format!(
+                    "<BooleanParameterType name=\"{}\">{}</BooleanParameterType>",
+                    name, encoding
+                )
+            } else {
+                format!(
+                    "<IntegerParameterType name=\"{}\" signed=\"{}\" sizeInBits=\"{}\">{}</IntegerParameterType>",
+                    name, signed, bits, encoding
+                )
+            };
+            let _ = writeln!(self.xml, "      {}", xml);
+        }
+        format!("/{}/{}", self.root, name)
+    }
+    fn type_xml(&mut self, name: &str, schema: &TypeSchema, big_endian: bool) -> String {
+        let xml = match schema {
+            TypeSchema::Primitive(primitive) => primitive_xml(name, *primitive, big_endian, ""),
+            TypeSchema::Array { len, element } if *len > 0 => {
+                let element = self.type_ref(element, big_endian);
+                format!(
+                    "<ArrayParameterType name=\"{}\" arrayTypeRef=\"{}\"><DimensionList><Dimension>\
+                     <StartingIndex><FixedValue>0</FixedValue></StartingIndex>\
+                     <EndingIndex><FixedValue>{}</FixedValue></EndingIndex>\
+                     </Dimension></DimensionList></ArrayParameterType>",
+                    name,
+                    element,
+                    len - 1
+                )
+            }
+            TypeSchema::String {
+                capacity,
+                length_bytes,
+            } => format!(
+                "<StringParameterType name=\"{}\"><StringDataEncoding encoding=\"UTF-8\">\
+                 <Variable maxSizeInBits=\"{}\"><LeadingSize sizeInBitsOfSizeTag=\"{}\"/></Variable>\
+                 </StringDataEncoding></StringParameterType>",
+                name,
+                capacity * 8,
+                *length_bytes as usize * 8
+            ),
+            // the engineering value is a float like `Scaled::value`
+            TypeSchema::Scaled { raw, scale, offset } => match **raw {
+                TypeSchema::Primitive(
+                    primitive @ (Primitive::Unsigned { .. } | Primitive::Signed { .. }),
+                ) => {
+                    let calibrator = format!(
+                        "<DefaultCalibrator><PolynomialCalibrator>\
+                         <Term coefficient=\"{}\" exponent=\"0\"/><Term coefficient=\"{}\" exponent=\"1\"/>\
+                         </PolynomialCalibrator></DefaultCalibrator>",
+                        offset,
+                        1. / *scale as f64
+                    );
+                    format!(
+                        "<FloatParameterType name=\"{}\" sizeInBits=\"32\">{}</FloatParameterType>",
+                        name,
+                        primitive_encoding(primitive, big_endian, &calibrator)
+                    )
+                }
+                _ => binary_xml(name, schema),
+            },
+            TypeSchema::Struct { fields, .. } if !fields.is_empty() => {
+                let members = self.members_xml(fields, big_endian);
+                format!(
+                    "<AggregateParameterType name=\"{}\"><MemberList>{}</MemberList></AggregateParameterType>",
+                    name, members
+                )
+            }
+            TypeSchema::Enum {
+                tag,
+                big_endian: tag_big_endian,
+                variants,
+                ..
+            } if variants.iter().all(|v| v.fields.is_empty()) => {
+                let encoding = primitive_encoding(*tag, tag_big_endian.unwrap_or(big_endian), "");
+                let enumerations: String = variants
+                    .iter()
+                    .map(|v| format!("<Enumeration value=\"{}\" label=\"{}\"/>", v.tag, v.name))
+                    .collect();
+                format!(
+                    "<EnumeratedParameterType name=\"{}\">{}<EnumerationList>{}</EnumerationList></EnumeratedParameterType>",
+                    name, encoding, enumerations
+                )
+            }
+            TypeSchema::Packed {
+                bit_order, fields, ..
+            } if !fields.is_empty() => {
+                let members: String = fields
+                    .iter()
+                    .map(|f| {
+                        let type_ref = self.bits_type_ref(f.primitive, f.bits, *bit_order);
+                        format!("<Member name=\"{}\" typeRef=\"{}\"/>", f.name, type_ref)
+                    })
+                    .collect();
+                let bits: u64 = fields.iter().map(|f| f.bits as u64).sum();
+                let padding = ((8 - bits % 8) % 8) as u32;
+                let padding = if padding > 0 {
+                    let type_ref =
+                        self.bits_type_ref(Primitive::Unsigned { bytes: 1 }, padding, *bit_order);
+                    format!("<Member name=\"padding\" typeRef=\"{}\"/>", type_ref)
+                } else {
+                    String::new()
+                };
+                format!(
+                    "<AggregateParameterType name=\"{}\"><MemberList>{}{}</MemberList></AggregateParameterType>",
+                    name, members, padding
+                )
+            }
+            _ => binary_xml(name, schema),
+        };
+        format!("      {}\n", xml)
+    }
+    fn members_xml(&mut self, fields: &[FieldSchema], big_endian: bool) -> String {
+        fields
+            .iter()
+            .map(|f| {
+                let type_ref = self.type_ref(&f.schema, f.big_endian.unwrap_or(big_endian));
+                format!("<Member name=\"{}\" typeRef=\"{}\"/>", f.name, type_ref)
+            })
+            .collect()
+    }
+}
+
+/// Local name of the parameter type of `schema`
+fn type_name(schema: &TypeSchema, big_endian: bool) -> String {
+    let order = if big_endian { "_be" } else { "_le" };
+    match schema {
+        TypeSchema::Unit => String::from("unit"),
+        TypeSchema::Primitive(primitive) => primitive_name(*primitive, big_endian),
+        TypeSchema::Array { len, element } => {
+            format!("{}_array_{}", type_name(element, big_endian), len)
+        }
+        TypeSchema::Vec {
+            capacity, element, ..
+        } => format!(
+            "{}_vec_{}{}",
+            type_name(element, big_endian),
+            capacity,
+            order
+        ),
+        TypeSchema::String { capacity, .. } => format!("string_{}{}", capacity, order),
+        TypeSchema::Option(value) => format!("{}_option", type_name(value, big_endian)),
+        TypeSchema::ByteOrder { big_endian, value } => type_name(value, *big_endian),
+        TypeSchema::Scaled { raw, scale, offset } => {
+            format!("{}_scaled_{}_{}", type_name(raw, big_endian), scale, offset).replace('-', "m")
+        }
+        TypeSchema::Struct { name, .. }
+        | TypeSchema::Enum { name, .. }
+        | TypeSchema::Packed { name, .. } => name.clone(),
+        TypeSchema::Opaque { size } => format!("opaque_{}", size),
+    }
+}
+
+fn primitive_name(primitive: Primitive, big_endian: bool) -> String {
+    let order = if big_endian { "_be" } else { "_le" };
+    match primitive {
+        Primitive::Bool => String::from("bool"),
+        Primitive::Char => format!("char{}", order),
+        Primitive::Unsigned { bytes: 1 } => String::from("u8"),
+        Primitive::Signed { bytes: 1 } => String::from("i8"),
+        Primitive::Unsigned { bytes } => format!("u{}{}", bytes as u32 * 8, order),
+        Primitive::Signed { bytes } => format!("i{}{}", bytes as u32 * 8, order),
+        Primitive::Float { bytes } => format!("f{}{}", bytes as u32 * 8, order),
+    }
+}
+
+fn integer_encoding(signed: bool) -> &'static str {
+    if signed { "twosComplement" } else { "unsigned" }
+}
+
+/// Data encoding of a primitive, `calibrator` is placed inside of it
+fn primitive_encoding(primitive: Primitive, big_endian: bool, calibrator: &str) -> String {
+    let bits = primitive.size() * 8;
+    let byte_order = match (bits > 8, big_endian) {
+        (false, _) => "",
+        (true, true) => " byteOrder=\"mostSignificantByteFirst\"",
+        (true, false) => " byteOrder=\"leastSignificantByteFirst\"",
+    };
+    let (element, encoding) = match primitive {
+        Primitive::Float { .. } => ("FloatDataEncoding", "IEEE754_1985"),
+        Primitive::Signed { .. } => ("IntegerDataEncoding", "twosComplement"),
+        _ => ("IntegerDataEncoding", "unsigned"),
+    };
+    let attributes = format!(
+        "sizeInBits=\"{}\" encoding=\"{}\"{}",
+        bits, encoding, byte_order
+    );
+    if calibrator.is_empty() {
+        format!("<{} {}/>", element, attributes)
+    } else {
+        format!("<{} {}>{}</{}>", element, attributes, calibrator, element)
+    }
+}
+
+fn primitive_xml(name: &str, primitive: Primitive, big_endian: bool, calibrator: &str) -> String {
+    let encoding = primitive_encoding(primitive, big_endian, calibrator);
+    let bits = primitive.size() * 8;
+    match primitive {
+        Primitive::Bool => format!(
+            "<BooleanParameterType name=\"{}\">{}</BooleanParameterType>",
+            name, encoding
+        ),
+        Primitive::Float { .. } => format!(
+            "<FloatParameterType name=\"{}\" sizeInBits=\"{}\">{}</FloatParameterType>",
+            name, bits, encoding
+        ),
+        _ => format!(
+            "<IntegerParameterType name=\"{}\" signed=\"{}\" sizeInBits=\"{}\">{}</IntegerParameterType>",
+            name,
+            matches!(primitive, Primitive::Signed { .. }),
+            bits,
+            encoding
+        ),
+    }
+}
+
+/// Types without a static layout, `schema` is kept as description
+fn binary_xml(name: &str, schema: &TypeSchema) -> String {
+    let size = schema.max_size().unwrap_or(usize::MAX);
+    let kind = match schema {
+        TypeSchema::Vec { .. } => "bounded vector",
+        TypeSchema::Option(_) => "option",
+        TypeSchema::Enum { .. } => "enum with fields",
+        TypeSchema::Opaque { .. } => "opaque value",
+        _ => "value",
+    };
+    format!(
+        "<BinaryParameterType name=\"{}\"><LongDescription>{}, up to {} bytes</LongDescription>\
+         <BinaryDataEncoding><SizeInBits><FixedValue>{}</FixedValue></SizeInBits></BinaryDataEncoding>\
+         </BinaryParameterType>",
+        name,
+        kind,
+        size,
+        size.saturating_mul(8)
+    )
+}
+
+/// Space system of a module with the parameters of its definitions
+struct SystemNode {
+    name: String,
+    parameters: String,
+    children: Vec<SystemNode>,
+}
+impl SystemNode {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            parameters: String::new(),
+            children: Vec::new(),
+        }
+    }
+    fn insert(&mut self, definition: &DefinitionSchema, types: &mut TypeSet) {
+        let type_ref = types.type_ref(&definition.schema, false);
+        let mut path = system_path(&self.name, &definition.address);
+        let name = path.pop().unwrap_or(&definition.address);
+        let mut node = self;
+        for module in path {
+            let i = match node.children.iter().position(|c| c.name == module) {
+                Some(i) => i,
+                None => {
+                    node.children.push(SystemNode::new(module));
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[i];
+        }
+        let calibrations: String = definition
+            .calibrations
+            .iter()
+            .map(|c| {
+                format!(
+                    "<AncillaryData name=\"calibration.{}\">{}</AncillaryData>",
+                    escape(&c.name),
+                    escape(&c.function)
+                )
+            })
+            .collect();
+        let _ = writeln!(
+            node.parameters,
+            "      <Parameter name=\"{}\" parameterTypeRef=\"{}\"><AncillaryDataSet>\
+             <AncillaryData name=\"id\">{}</AncillaryData>{}</AncillaryDataSet></Parameter>",
+            name, type_ref, definition.id, calibrations
+        );
+    }
+    fn write(&self, xml: &mut String, depth: usize, root: Option<(&String, &String)>) {
+        let indent = "  ".repeat(depth);
+        match root {
+            Some(_) => {
+                let _ = writeln!(
+                    xml,
+                    "{}<SpaceSystem xmlns=\"{}\" name=\"{}\">",
+                    indent, XTCE_NAMESPACE, self.name
+                );
+            }
+            None => {
+                let _ = writeln!(xml, "{}<SpaceSystem name=\"{}\">", indent, self.name);
+            }
+        }
+        let (types, containers) = root
+            .map(|(t, c)| (t.as_str(), c.as_str()))
+            .unwrap_or_default();
+        if !(types.is_empty() && self.parameters.is_empty() && containers.is_empty()) {
+            let _ = writeln!(xml, "{}  <TelemetryMetaData>", indent);
+            for (set, content) in [
+                ("ParameterTypeSet", types),
+                ("ParameterSet", &self.parameters),
+                ("ContainerSet", containers),
+            ] {
+                if content.is_empty() {
+                    continue;
+                }
+                let _ = writeln!(xml, "{}    <{}>", indent, set);
+                for line in content.lines() {
+                    let _ = writeln!(xml, "{}{}", indent, line);
+                }
+                let _ = writeln!(xml, "{}    </{}>", indent, set);
+            }
+            let _ = writeln!(xml, "{}  </TelemetryMetaData>", indent);
+        }
+        for child in &self.children {
+            child.write(xml, depth + 1, None);
+        }
+        let _ = writeln!(xml, "{}</SpaceSystem>", indent);
+    }
+}
+
+/// Sequence container of a beacon frame: id, crc, presence bits, timestamp and the
+/// present values. The presence bits are listed in frame order, most significant first
+fn beacon_container(
+    beacon: &BeaconSchema,
+    parameters: &mut String,
+    containers: &mut String,
+    types: &mut TypeSet,
+) {
+    let crc_type = types.type_ref(
+        &TypeSchema::Primitive(Primitive::Unsigned {
+            bytes: beacon.crc.size() as u8,
+        }),
+        false,
+    );
+    let flag_type = types.bits_type_ref(Primitive::Unsigned { bytes: 1 }, 1, BitOrder::Msb);
+    let _ = writeln!(
+        parameters,
+        "      <Parameter name=\"{}_crc\" parameterTypeRef=\"{}\"/>",
+        beacon.name, crc_type
+    );
+    let flags = beacon.values.len().div_ceil(8) * 8;
+    for i in 0..flags {
+        let _ = writeln!(
+            parameters,
+            "      <Parameter name=\"{}_present_{}\" parameterTypeRef=\"{}\"/>",
+            beacon.name, i, flag_type
+        );
+    }
+
+    let mut entries = Vec::new();
+    entries.push(format!(
+        "<FixedValueEntry name=\"id\" binaryValue=\"{:02X}\" sizeInBits=\"8\"/>",
+        beacon.id
+    ));
+    entries.push(format!(
+        "<ParameterRefEntry parameterRef=\"{}_crc\"/>",
+        beacon.name
+    ));
+    if let Some(hash) = beacon.dictionary_hash {
+        let bytes: String = hash
+            .to_le_bytes()
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect();
+        entries.push(format!(
+            "<FixedValueEntry name=\"dictionary_hash\" binaryValue=\"{}\" sizeInBits=\"32\"/>",
+            bytes
+        ));
+    }
+    // the bitfield holds value i at bit i % 8 of byte i / 8, counted from the least significant bit
+    for byte in 0..flags / 8 {
+        for bit in (0..8).rev() {
+            entries.push(format!(
+                "<ParameterRefEntry parameterRef=\"{}_present_{}\"/>",
+                beacon.name,
+                byte * 8 + bit
+            ));
+        }
+    }
+    entries.push(format!(
+        "<ParameterRefEntry parameterRef=\"{}\"/>",
+        parameter_ref(&types.root, &beacon.timestamp)
+    ));
+    for (i, value) in beacon.values.iter().enumerate() {
+        entries.push(format!(
+            "<ParameterRefEntry parameterRef=\"{}\"><IncludeCondition>\
+             <Comparison parameterRef=\"{}_present_{}\" value=\"1\"/></IncludeCondition></ParameterRefEntry>",
+            parameter_ref(&types.root, value),
+            beacon.name,
+            i
+        ));
+    }
+
+    let _ = writeln!(
+        containers,
+        "      <SequenceContainer name=\"{}\"><EntryList>",
+        beacon.name
+    );
+    for entry in entries {
+        let _ = writeln!(containers, "        {}", entry);
+    }
+    let _ = writeln!(containers, "      </EntryList></SequenceContainer>");
+}
+
+/// Space systems below the root down to the parameter of `address`, the parameter
+/// name last. Addresses of other trees keep their root module as first space system
+fn system_path<'a>(root: &str, address: &'a str) -> Vec<&'a str> {
+    let mut path: Vec<_> = address.split('.').collect();
+    if path.len() > 1 && path[0] == root {
+        path.remove(0);
+    }
+    path
+}
+
+/// Absolute reference of the parameter of a definition
+fn parameter_ref(root: &str, definition: &DefinitionSchema) -> String {
+    let path = system_path(root, &definition.address);
+    format!("/{}/{}", root, path.join("/"))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -4,12 +4,14 @@ use tmtc_system::*;
 
 #[derive(TMValue, Default, Clone, Copy)]
 #[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
 pub struct TestValue {
     val: u32,
 }
 
 #[derive(TMValue, Default, Clone, Copy)]
 #[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
 pub struct TestVector {
     x: i16,
     y: f32,
@@ -42,6 +44,33 @@ beacon!(
     values(FirstTMValue, SecondTMValue, some_other_mod::ThirdTMValue)
 );
 
+#[derive(TMValue, Default, Clone, Copy)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+#[tm(packed)]
+pub struct PackedMode {
+    #[tm(bits = 4)]
+    mode: u8,
+    #[tm(bits = 4)]
+    level: u8,
+}
+
+#[telemetry_definition(id = 0)]
+mod status {
+    #[tmv(i64)]
+    struct Timestamp;
+    #[tmv(crate::PackedMode)]
+    struct Mode;
+}
+
+beacon!(
+    StatusBeacon,
+    crate::status,
+    crate::status::Timestamp,
+    id = 1,
+    values(Mode)
+);
+
 macro_rules! to_bytes {
     ($type: ty, $tm_value:ident) => {{
         let mut bytes = [0u8; <$type>::MAX_BYTE_SIZE];
@@ -50,21 +79,6 @@ macro_rules! to_bytes {
     }};
 }
 
-fn crc_ccitt(bytes: &[u8]) -> u16 {
-    let mut crc: u16 = 0xFFFF;
-    for byte in bytes {
-        crc ^= (*byte as u16) << 8;
-        for _ in 0..8 {
-            if (crc & 0x8000) != 0 {
-                crc = (crc << 1) ^ 0x1021;
-            } else {
-                crc <<= 1;
-            }
-        }
-    }
-    crc
-}
-
 #[test]
 fn beacon_creation() {
     let mut beacon = TestBeacon::new();
@@ -82,7 +96,7 @@ fn beacon_creation() {
     beacon.some_other_mod_third_tm_value = Some(third_value);
 
     let sizes = [3, 1, 8, 4, (4), (2 + 4 + 4)];
-    assert_eq!(beacon.to_bytes(&mut crc_ccitt).len(), sizes.iter().sum());
+    assert_eq!(beacon.to_bytes().unwrap().len(), sizes.iter().sum());
 }
 
 #[test]
@@ -101,8 +115,8 @@ fn beacon_insertion() {
     beacon.second_tm_value = Some(second_value);
     beacon.some_other_mod_third_tm_value = Some(third_value);
 
-    let bytes = beacon.to_bytes(&mut crc_ccitt);
-    let crc = crc_ccitt(&bytes[3..]);
+    let bytes = beacon.to_bytes().unwrap();
+    let crc = crc::ccitt16(&bytes[3..]);
     // calculated with
     // https://www.crccalc.com/?crc=00, 00, 00, 00, 00, 00, 00, 00, D2, 04, 00, 00, 03, 00, 00, 00, 03, 00, 33, 33, 53, 40, 01, 00, 00, 00&method=CRC-16/CCITT-FALSE&datatype=hex&outtype=hex
     // assert_eq!(crc, 0x8798);
@@ -149,10 +163,7 @@ fn beacon_insertion_id() {
     beacon.second_tm_value = Some(second_value);
     beacon.some_other_mod_third_tm_value = Some(third_value);
 
-    assert_eq!(
-        id_beacon.to_bytes(&mut crc_ccitt),
-        beacon.to_bytes(&mut crc_ccitt)
-    );
+    assert_eq!(id_beacon.to_bytes().unwrap(), beacon.to_bytes().unwrap());
 }
 
 #[test]
@@ -192,7 +203,104 @@ fn beacon_insertion_address() {
     beacon.some_other_mod_third_tm_value = Some(third_value);
 
     assert_eq!(
-        address_beacon.to_bytes(&mut crc_ccitt),
-        beacon.to_bytes(&mut crc_ccitt)
+        address_beacon.to_bytes().unwrap(),
+        beacon.to_bytes().unwrap()
+    );
+}
+
+beacon!(
+    Crc32Beacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 1,
+    values(FirstTMValue),
+    crc = crc32c
+);
+
+#[test]
+fn beacon_crc_kind() {
+    let mut beacon = Crc32Beacon::new();
+    beacon.first_tm_value = Some(1234);
+
+    let bytes = beacon.to_bytes().unwrap();
+    assert_eq!(bytes.len(), 1 + 4 + 1 + 8 + 4);
+    assert_eq!(bytes[1..5], crc::crc32c(&bytes[5..]).to_le_bytes());
+
+    let mut received = Crc32Beacon::new();
+    let mut corrupted = [0u8; Crc32Beacon::BYTE_SIZE];
+    corrupted[..bytes.len()].copy_from_slice(bytes);
+    received.from_bytes(&corrupted[..bytes.len()]).unwrap();
+    assert_eq!(received.first_tm_value, Some(1234));
+
+    corrupted[10] ^= 1;
+    assert!(matches!(
+        received.from_bytes(&corrupted[..bytes.len()]),
+        Err(ParseError::BadCRC { .. })
+    ));
+}
+
+#[test]
+fn beacon_parse_errors() {
+    let mut beacon = TestBeacon::new();
+    beacon.some_other_mod_third_tm_value = Some(TestVector::default());
+    let mut bytes = [0u8; TestBeacon::BYTE_SIZE];
+    let len = {
+        let valid = beacon.to_bytes().unwrap();
+        bytes[..valid.len()].copy_from_slice(valid);
+        valid.len()
+    };
+
+    let mut received = TestBeacon::new();
+    let error = received.from_bytes(&bytes[..2]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "frame too short: expected 4 header bytes, 2 available"
+    );
+
+    // cut the frame inside the vector and fix up the crc
+    let truncated = len - 3;
+    let crc = crc::ccitt16(&bytes[3..truncated]);
+    bytes[1..3].copy_from_slice(&crc.to_le_bytes());
+    let error = received.from_bytes(&bytes[..truncated]).unwrap_err();
+    assert!(matches!(
+        error,
+        ParseError::InvalidValue {
+            address: "telemetry.some_other_mod.third_tm_value",
+            offset: 12,
+            expected: 10,
+            available: 7,
+            error: TMValueError::OutOfMemory,
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "could not parse telemetry.some_other_mod.third_tm_value at byte offset 12 \
+         (up to 10 bytes expected, 7 available): out of memory"
+    );
+
+    bytes[0] = 5;
+    assert_eq!(
+        received.from_bytes(&bytes[..len]).unwrap_err().to_string(),
+        "wrong id: expected 0, found 5"
     );
 }
+
+#[test]
+fn beacon_rejects_out_of_range_values() {
+    let mut beacon = StatusBeacon::new();
+    beacon.mode = Some(PackedMode { mode: 3, level: 2 });
+    assert!(beacon.to_bytes().is_ok());
+
+    // 200 does not fit into the 4 bits of the mode
+    beacon.mode = Some(PackedMode {
+        mode: 200,
+        level: 2,
+    });
+    assert!(matches!(
+        beacon.to_bytes(),
+        Err(BeaconOperationError::InvalidValue {
+            address: "status.mode",
+            error: TMValueError::BadValue
+        })
+    ));
+}
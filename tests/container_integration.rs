@@ -7,12 +7,14 @@ extern crate alloc;
 
 #[derive(TMValue, Default, Clone, Copy)]
 #[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
 pub struct TestValue {
     val: u32,
 }
 
 #[derive(TMValue, Default, Clone, Copy)]
 #[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
 pub struct TestVector {
     x: i16,
     y: f32,
@@ -38,6 +40,8 @@ mod telemetry {
         #[tmv(crate::TestValue)]
         struct FifthTMValue;
     }
+    #[tmv(u32, endian = big)]
+    struct NetworkValue;
 }
 
 type PartialTestContainer = fd_compat_telemetry_container!(telemetry::some_other_mod);
@@ -75,3 +79,10 @@ fn full_container_creation() {
     assert_eq!(container.bytes()[2..6], 24f32.to_le_bytes());
     assert_eq!(container.bytes()[6..10], 36u32.to_le_bytes());
 }
+
+#[test]
+fn big_endian_definition() {
+    let container =
+        FullTestContainer::new(&telemetry::NetworkValue, &BigEndian(0x01020304)).unwrap();
+    assert_eq!(container.bytes(), [1, 2, 3, 4]);
+}
@@ -0,0 +1,22 @@
+use tmtc_system::crc::*;
+
+const CHECK: &[u8] = b"123456789";
+
+#[test]
+fn crc_check_values() {
+    assert_eq!(ccitt16(CHECK), 0x29B1);
+    assert_eq!(x25(CHECK), 0x906E);
+    assert_eq!(crc32c(CHECK), 0xE3069283);
+}
+
+#[test]
+fn crc_kind_round_trip() {
+    for kind in [CrcKind::Ccitt16, CrcKind::X25, CrcKind::Crc32c] {
+        let mut mem = [0u8; 4];
+        kind.write(CHECK, &mut mem);
+        assert_eq!(kind.read(&mem), kind.checksum(CHECK));
+        assert!(mem[kind.size()..].iter().all(|b| *b == 0));
+    }
+    const CONST_CRC: u16 = ccitt16(CHECK);
+    assert_eq!(CONST_CRC, 0x29B1);
+}
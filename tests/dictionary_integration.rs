@@ -0,0 +1,248 @@
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+#[derive(TMValue, Default, Clone, Copy)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+pub struct Vector {
+    x: i16,
+    y: i16,
+}
+
+#[derive(TMValue, Default, Clone, Copy)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+pub struct WideVector {
+    x: i32,
+    y: i16,
+}
+
+// flight and ground builds of the same dictionary
+mod flight {
+    #[tmtc_system::telemetry_definition(id = 0)]
+    pub mod telemetry {
+        #[tmv(u32)]
+        struct Timestamp;
+        #[tmv(crate::Vector)]
+        struct Position;
+        #[tmm(id = 10)]
+        mod power {
+            #[tmv(u16)]
+            struct Voltage;
+        }
+    }
+
+    tmtc_system::beacon!(
+        PowerBeacon,
+        crate::flight::telemetry,
+        crate::flight::telemetry::Timestamp,
+        id = 1,
+        values(Position, power::Voltage),
+        dictionary_hash = true
+    );
+}
+
+mod ground {
+    #[tmtc_system::telemetry_definition(id = 0)]
+    pub mod telemetry {
+        #[tmv(u32)]
+        struct Timestamp;
+        #[tmv(crate::Vector)]
+        struct Position;
+        #[tmm(id = 10)]
+        mod power {
+            #[tmv(u16)]
+            struct Voltage;
+        }
+    }
+}
+
+// ground build after the position type changed
+mod outdated {
+    #[tmtc_system::telemetry_definition(id = 0)]
+    pub mod telemetry {
+        #[tmv(u32)]
+        struct Timestamp;
+        #[tmv(crate::WideVector)]
+        struct Position;
+        #[tmm(id = 10)]
+        mod power {
+            #[tmv(u16)]
+            struct Voltage;
+        }
+    }
+
+    tmtc_system::beacon!(
+        PowerBeacon,
+        crate::outdated::telemetry,
+        crate::outdated::telemetry::Timestamp,
+        id = 1,
+        values(Position, power::Voltage),
+        dictionary_hash = true
+    );
+}
+
+// same layouts under a different id
+mod renumbered {
+    #[tmtc_system::telemetry_definition(id = 0)]
+    pub mod telemetry {
+        #[tmv(u32)]
+        struct Timestamp;
+        #[tmv(crate::Vector)]
+        struct Position;
+        #[tmm(id = 20)]
+        mod power {
+            #[tmv(u16)]
+            struct Voltage;
+            mod battery {
+                #[tmv(i8)]
+                struct Temperature;
+            }
+        }
+    }
+}
+
+// beacons rooted at the crate, hashed from the definitions they send
+tmtc_system::beacon!(
+    RootBeacon,
+    crate,
+    crate::flight::telemetry::Timestamp,
+    id = 1,
+    values(
+        flight::telemetry::Position,
+        flight::telemetry::power::Voltage
+    ),
+    dictionary_hash = true
+);
+
+tmtc_system::beacon!(
+    MixedBeacon,
+    crate,
+    crate::flight::telemetry::Timestamp,
+    id = 1,
+    values(flight::telemetry::Position, verification::CompletionSuccess),
+    dictionary_hash = true
+);
+
+#[test]
+fn dictionary_hash() {
+    assert_eq!(
+        flight::telemetry::DICTIONARY_HASH,
+        ground::telemetry::DICTIONARY_HASH
+    );
+    assert_ne!(
+        flight::telemetry::DICTIONARY_HASH,
+        outdated::telemetry::DICTIONARY_HASH
+    );
+    assert_ne!(
+        flight::telemetry::DICTIONARY_HASH,
+        renumbered::telemetry::DICTIONARY_HASH
+    );
+}
+
+#[test]
+fn definitions() {
+    let listed: Vec<_> = renumbered::telemetry::DEFINITIONS
+        .iter()
+        .map(|d| (d.id(), d.address()))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            (0, "telemetry.timestamp"),
+            (1, "telemetry.position"),
+            (20, "telemetry.power.voltage"),
+            (21, "telemetry.power.battery.temperature"),
+        ]
+    );
+    // submodules list their own subtree
+    let power: Vec<_> = renumbered::telemetry::power::DEFINITIONS
+        .iter()
+        .map(|d| d.id())
+        .collect();
+    assert_eq!(power, [20, 21]);
+    assert_eq!(
+        renumbered::telemetry::power::battery::DEFINITIONS[0].address(),
+        "telemetry.power.battery.temperature"
+    );
+}
+
+#[test]
+fn layout_hash() {
+    assert_ne!(<u16 as TMValue>::LAYOUT_HASH, <i16 as TMValue>::LAYOUT_HASH);
+    assert_ne!(
+        <BigEndian<u16> as TMValue>::LAYOUT_HASH,
+        <u16 as TMValue>::LAYOUT_HASH
+    );
+    assert_ne!(
+        <[u8; 2] as TMValue>::LAYOUT_HASH,
+        <u16 as TMValue>::LAYOUT_HASH
+    );
+    assert_ne!(
+        <Vector as TMValue>::LAYOUT_HASH,
+        <WideVector as TMValue>::LAYOUT_HASH
+    );
+}
+
+#[test]
+fn beacon_dictionary_mismatch() {
+    let mut beacon = flight::PowerBeacon::new();
+    beacon.timestamp = 12;
+    beacon.power_voltage = Some(3300);
+    let bytes = beacon.to_bytes().unwrap().to_vec();
+
+    let mut received = flight::PowerBeacon::new();
+    received.from_bytes(&bytes).unwrap();
+    assert_eq!(received.power_voltage, Some(3300));
+
+    let mut outdated = outdated::PowerBeacon::new();
+    let Err(ParseError::DictionaryMismatch { expected, found }) = outdated.from_bytes(&bytes)
+    else {
+        panic!("expected a dictionary mismatch");
+    };
+    assert_eq!(expected, outdated::PowerBeacon::DICTIONARY_HASH);
+    assert_eq!(found, flight::PowerBeacon::DICTIONARY_HASH);
+}
+
+#[test]
+fn beacon_dictionary_hash() {
+    // the hash follows the sent definitions, not the path they are named by
+    assert_eq!(
+        RootBeacon::DICTIONARY_HASH,
+        flight::PowerBeacon::DICTIONARY_HASH
+    );
+    assert_ne!(MixedBeacon::DICTIONARY_HASH, RootBeacon::DICTIONARY_HASH);
+
+    let mut beacon = MixedBeacon::new();
+    beacon.verification_completion_success = Some(ExecutionReport::new(3, 9));
+    let bytes = beacon.to_bytes().unwrap().to_vec();
+    let mut received = MixedBeacon::new();
+    received.from_bytes(&bytes).unwrap();
+    assert_eq!(
+        received.verification_completion_success,
+        beacon.verification_completion_success
+    );
+    assert!(matches!(
+        RootBeacon::new().from_bytes(&bytes),
+        Err(ParseError::DictionaryMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn schema_dictionary_mismatch() {
+    let mut beacon = flight::PowerBeacon::new();
+    beacon.position = Some(Vector { x: 1, y: -1 });
+    let bytes = beacon.to_bytes().unwrap().to_vec();
+
+    let decoded = flight::PowerBeacon::schema().decode(&bytes).unwrap();
+    assert_eq!(decoded.values.len(), 1);
+    assert!(matches!(
+        outdated::PowerBeacon::schema().decode(&bytes),
+        Err(schema::DecodeError::DictionaryMismatch { .. })
+    ));
+}
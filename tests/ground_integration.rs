@@ -5,12 +5,14 @@
 use tmtc_system::*;
 extern crate alloc;
 
-#[derive(TMValue, Default, Clone, Copy, serde::Serialize)]
+#[derive(TMValue, Default, Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
 pub struct TestValue {
     pub val: u32,
 }
 
-#[derive(TMValue, Default, Clone, Copy, serde::Serialize)]
+#[derive(TMValue, Default, Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
 pub struct TestVector {
     x: i16,
     y: f32,
@@ -83,3 +85,75 @@ fn tm_serialize() {
         assert_eq!(ser.0, address);
     }
 }
+
+#[cfg(feature = "ground-deserialize")]
+struct CborDeserializer;
+#[cfg(feature = "ground-deserialize")]
+impl ground_tm::Deserializer for CborDeserializer {
+    type Error = serde_cbor::Error;
+    fn deserialize_value<V: serde::de::DeserializeOwned>(
+        &self,
+        bytes: &[u8],
+    ) -> Result<V, Self::Error> {
+        serde_cbor::from_slice(bytes)
+    }
+}
+
+#[cfg(feature = "ground-deserialize")]
+#[test]
+fn tm_deserialize_definition() {
+    use ground_tm::{DeserializableTMValue, SerializableTMValue};
+    let value = TestValue { val: 42 };
+    let serialized = value
+        .serialize_ground(&telemetry::SecondTMValue, 7i64, &CborSerializer)
+        .unwrap();
+    let (address, bytes) = serialized.last().unwrap();
+    assert_eq!(*address, "telemetry.second_tm_value");
+
+    let telemetry: ground_tm::GroundTelemetry<i64, TestValue> =
+        TestValue::deserialize_ground(&telemetry::SecondTMValue, bytes, &CborDeserializer).unwrap();
+    assert_eq!(*telemetry.timestamp(), 7);
+    assert_eq!(*telemetry.value(), value);
+}
+
+#[cfg(feature = "ground-deserialize")]
+#[test]
+fn tm_deserialize_beacon() {
+    let mut beacon = test_beacon::TestBeacon::new();
+    beacon.timestamp = 1000;
+    beacon.first_tm_value = Some(1234);
+    beacon.some_other_mod_third_tm_value = Some(TestVector {
+        x: -3,
+        y: 3.5,
+        z: TestValue { val: 1 },
+    });
+
+    let serialized_pairs = beacon.serialize(&CborSerializer).unwrap();
+    let rebuilt =
+        test_beacon::TestBeacon::deserialize(serialized_pairs, &CborDeserializer).unwrap();
+    assert_eq!(rebuilt.timestamp, 1000);
+    assert_eq!(rebuilt.first_tm_value, Some(1234));
+    assert_eq!(rebuilt.second_tm_value, None);
+    assert_eq!(
+        rebuilt.some_other_mod_third_tm_value,
+        beacon.some_other_mod_third_tm_value
+    );
+}
+
+#[cfg(feature = "ground-deserialize")]
+#[test]
+fn tm_deserialize_beacon_timestamp_mismatch() {
+    let mut beacon = test_beacon::TestBeacon::new();
+    beacon.timestamp = 1000;
+    beacon.first_tm_value = Some(1234);
+    let mut later = test_beacon::TestBeacon::new();
+    later.timestamp = 2000;
+    later.second_tm_value = Some(TestValue { val: 5 });
+
+    let mut serialized_pairs = beacon.serialize(&CborSerializer).unwrap();
+    serialized_pairs.append(&mut later.serialize(&CborSerializer).unwrap());
+    assert!(matches!(
+        test_beacon::TestBeacon::deserialize(serialized_pairs, &CborDeserializer),
+        Err(ground_tm::BeaconDeserializeError::TimestampMismatch)
+    ));
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "heapless")]
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::heapless::{String, Vec};
+use tmtc_system::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+pub struct StarTracker {
+    quality: u8,
+    star_ids: Vec<u16, 8>,
+}
+
+#[telemetry_definition(id = 0)]
+mod telemetry {
+    #[tmv(u32)]
+    struct Timestamp;
+    #[tmv(crate::StarTracker)]
+    struct StarTracker;
+    #[tmv(heapless::Vec<u8, 300>)]
+    struct ErrorLog;
+    #[tmv(heapless::String<16>)]
+    struct FirmwareVersion;
+}
+
+beacon!(
+    ListBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 3,
+    values(StarTracker, ErrorLog, FirmwareVersion)
+);
+
+#[test]
+fn vec_round_trip() {
+    let value = StarTracker {
+        quality: 7,
+        star_ids: Vec::from_slice(&[12, 400, 9]).unwrap(),
+    };
+    assert_eq!(StarTracker::MAX_BYTE_SIZE, 1 + 1 + 8 * 2);
+
+    let mut mem = [0u8; StarTracker::MAX_BYTE_SIZE];
+    let len = value.write(&mut mem).unwrap();
+    assert_eq!(len, 1 + 1 + 3 * 2);
+    assert_eq!(mem[..4], [7, 3, 12, 0]);
+
+    let (read, copy) = StarTracker::read(&mem[..len]).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(copy, value);
+}
+
+#[test]
+fn vec_length_prefix() {
+    // the prefix grows with the capacity
+    assert_eq!(<Vec<u8, 255>>::MAX_BYTE_SIZE, 1 + 255);
+    assert_eq!(<Vec<u8, 300>>::MAX_BYTE_SIZE, 2 + 300);
+
+    let log: Vec<u8, 300> = Vec::from_slice(&[1, 2]).unwrap();
+    let mut mem = [0u8; 4];
+    assert_eq!(log.write(&mut mem).unwrap(), 4);
+    assert_eq!(mem, [2, 0, 1, 2]);
+
+    // lengths above the capacity and truncated elements are rejected
+    assert!(matches!(
+        <Vec<u16, 8>>::read(&[9; 32]),
+        Err(TMValueError::TooLong)
+    ));
+    assert!(matches!(
+        <Vec<u16, 8>>::read(&[2, 1, 0, 1]),
+        Err(TMValueError::OutOfMemory)
+    ));
+}
+
+#[test]
+fn vec_in_beacon() {
+    let mut beacon = ListBeacon::new();
+    let value = StarTracker {
+        quality: 1,
+        star_ids: Vec::from_slice(&[5, 6]).unwrap(),
+    };
+    beacon.star_tracker = Some(value.clone());
+    beacon.error_log = Some(Vec::from_slice(&[0xEE; 20]).unwrap());
+
+    let bytes = beacon.to_bytes().unwrap().to_vec();
+    let mut copy = ListBeacon::new();
+    copy.from_bytes(&bytes).unwrap();
+    assert_eq!(copy.star_tracker, Some(value));
+    assert_eq!(copy.error_log.unwrap().len(), 20);
+}
+
+#[test]
+fn string_round_trip() {
+    let version: String<16> = String::try_from("v4.7.0-ß").unwrap();
+    assert_eq!(<String<16>>::MAX_BYTE_SIZE, 1 + 16);
+
+    let mut mem = [0u8; 17];
+    let len = version.write(&mut mem).unwrap();
+    assert_eq!(len, 1 + 9);
+    assert_eq!(mem[..3], [9, b'v', b'4']);
+    assert_eq!(<String<16>>::read(&mem[..len]).unwrap(), (len, version));
+
+    assert!(matches!(
+        <String<16>>::read(&[2, 0xC3, 0x28]),
+        Err(TMValueError::InvalidUtf8)
+    ));
+    assert!(matches!(
+        <String<16>>::read(&[17; 20]),
+        Err(TMValueError::TooLong)
+    ));
+    assert!(matches!(
+        <String<16>>::read(&[4, b'a']),
+        Err(TMValueError::OutOfMemory)
+    ));
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn string_serialization() {
+    use serde_cbor::Value;
+
+    struct CborSerializer;
+    impl ground_tm::Serializer for CborSerializer {
+        type Error = serde_cbor::Error;
+        fn serialize_value<T: serde::Serialize>(
+            &self,
+            value: &T,
+        ) -> Result<std::vec::Vec<u8>, Self::Error> {
+            serde_cbor::to_vec(value)
+        }
+    }
+
+    let mut beacon = ListBeacon::new();
+    beacon.firmware_version = Some(String::try_from("v1.2").unwrap());
+    let serialized = beacon.serialize(&CborSerializer).unwrap();
+    let (address, bytes) = &serialized[0];
+    assert_eq!(*address, "telemetry.firmware_version");
+
+    // arrives as a text string, not as a byte array
+    let Value::Map(telemetry) = serde_cbor::from_slice(bytes).unwrap() else {
+        panic!("ground telemetry should be a map");
+    };
+    assert_eq!(
+        telemetry[&Value::Text("value".into())],
+        Value::Text("v1.2".into())
+    );
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn bounded_schema_decode() {
+    use schema::DynamicValue;
+
+    let mut beacon = ListBeacon::new();
+    beacon.error_log = Some(Vec::from_slice(&[4, 2]).unwrap());
+    beacon.firmware_version = Some(String::try_from("v1.2").unwrap());
+    let bytes = beacon.to_bytes().unwrap().to_vec();
+
+    let decoded = ListBeacon::schema().decode(&bytes).unwrap();
+    let values: std::vec::Vec<_> = decoded.values.into_iter().map(|v| v.value).collect();
+    assert_eq!(
+        values,
+        [
+            DynamicValue::List(vec![DynamicValue::Unsigned(4), DynamicValue::Unsigned(2)]),
+            DynamicValue::String("v1.2".into()),
+        ]
+    );
+}
@@ -0,0 +1,184 @@
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::pus::*;
+use tmtc_system::space_packet::*;
+use tmtc_system::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+#[derive(TMValue, Default, Clone, Copy)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+#[tm(packed)]
+pub struct HeaterStatus {
+    on: bool,
+    #[tm(bits = 3)]
+    level: u8,
+}
+
+#[telemetry_definition(id = 0)]
+mod telemetry {
+    #[tmv(u32)]
+    struct Timestamp;
+    #[tmv(u16)]
+    struct BatteryVoltage;
+    #[tmv(i8)]
+    struct Temperature;
+    #[tmv(crate::HeaterStatus)]
+    struct Heater;
+}
+
+beacon!(
+    HousekeepingBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 12,
+    values(BatteryVoltage, Temperature, Heater),
+    pus = true
+);
+
+fn housekeeping_request(subtype: u8, app_data: &[u8], mem: &mut [u8]) -> usize {
+    let header = PusTcSecondaryHeader {
+        acknowledgement_flags: 0,
+        service: HOUSEKEEPING_SERVICE,
+        subtype,
+        source_id: 1,
+    };
+    let len = header.write(mem).unwrap();
+    mem[len..len + app_data.len()].copy_from_slice(app_data);
+    len + app_data.len()
+}
+
+#[test]
+fn housekeeping_report() {
+    let mut beacon = HousekeepingBeacon::new();
+    beacon.battery_voltage = Some(3300);
+    beacon.temperature = Some(-2);
+    beacon.heater = Some(HeaterStatus { on: true, level: 5 });
+    let time = CucTime::new(0x01020304, 0x8000);
+
+    let mut mem = [0u8; 64];
+    let len = beacon
+        .housekeeping_report(5, &time, &mut mem[PRIMARY_HEADER_SIZE..])
+        .unwrap();
+    let mut encoder = SpacePacketEncoder::for_beacon(&beacon);
+    let packet_len = encoder.encode_header(true, len, &mut mem).unwrap();
+
+    let packet = SpacePacket::decode(&mem[..packet_len]).unwrap();
+    assert_eq!(packet.header.apid, 12);
+    assert_eq!(
+        packet.data,
+        [
+            0x20,
+            3,
+            25,
+            0,
+            0,
+            0,
+            5, // secondary header
+            1,
+            2,
+            3,
+            4,
+            0x80,
+            0, // cuc time
+            0,
+            12, // structure id
+            0x0C,
+            0xE4,        // battery voltage
+            0xFE,        // temperature
+            0b1101_0000, // heater
+        ]
+    );
+
+    // a failed report does not use up a message counter
+    assert!(matches!(
+        beacon.housekeeping_report(5, &time, &mut mem[..12]),
+        Err(PusError::OutOfMemory)
+    ));
+    beacon.housekeeping_report(5, &time, &mut mem).unwrap();
+    assert_eq!(mem[3..5], 1u16.to_be_bytes());
+}
+
+#[test]
+fn housekeeping_report_failures() {
+    let mut beacon = HousekeepingBeacon::new();
+    let time = CucTime::default();
+    let mut mem = [0u8; 64];
+    beacon.battery_voltage = Some(3300);
+    beacon.heater = Some(HeaterStatus { on: true, level: 5 });
+    assert!(matches!(
+        beacon.housekeeping_report(5, &time, &mut mem),
+        Err(PusError::MissingParameter("telemetry.temperature"))
+    ));
+
+    // the level does not fit its 3 bits
+    beacon.temperature = Some(20);
+    beacon.heater = Some(HeaterStatus { on: true, level: 9 });
+    assert!(matches!(
+        beacon.housekeeping_report(5, &time, &mut mem),
+        Err(PusError::InvalidValue(TMValueError::BadValue))
+    ));
+    assert_eq!(beacon.housekeeping.message_counter(), 0);
+}
+
+#[test]
+fn housekeeping_requests() {
+    let mut beacon = HousekeepingBeacon::new();
+    let mut mem = [0u8; 32];
+    assert!(!beacon.housekeeping.tick());
+
+    let len = housekeeping_request(ENABLE_PERIODIC_GENERATION, &[2, 0, 3, 0, 12], &mut mem);
+    assert!(beacon.handle_housekeeping_request(&mem[..len]).unwrap());
+    assert!(beacon.housekeeping.is_enabled());
+    assert!(beacon.housekeeping.tick());
+
+    let len = housekeeping_request(
+        MODIFY_COLLECTION_INTERVAL,
+        &[1, 0, 12, 0, 0, 0, 3],
+        &mut mem,
+    );
+    assert!(beacon.handle_housekeeping_request(&mem[..len]).unwrap());
+    assert_eq!(beacon.housekeeping.interval(), 3);
+    assert!(!beacon.housekeeping.tick());
+    assert!(!beacon.housekeeping.tick());
+    assert!(beacon.housekeeping.tick());
+
+    let len = housekeeping_request(DISABLE_PERIODIC_GENERATION, &[1, 0, 4], &mut mem);
+    assert!(!beacon.handle_housekeeping_request(&mem[..len]).unwrap());
+    assert!(beacon.housekeeping.is_enabled());
+
+    let len = housekeeping_request(DISABLE_PERIODIC_GENERATION, &[1, 0, 12], &mut mem);
+    assert!(beacon.handle_housekeeping_request(&mem[..len]).unwrap());
+    assert!(!beacon.housekeeping.tick());
+
+    let len = housekeeping_request(DISABLE_PERIODIC_GENERATION, &[2, 0, 12], &mut mem);
+    assert!(matches!(
+        beacon.handle_housekeeping_request(&mem[..len]),
+        Err(PusError::MalformedRequest)
+    ));
+    // a malformed later entry leaves earlier entries unapplied
+    let len = housekeeping_request(ENABLE_PERIODIC_GENERATION, &[2, 0, 12, 0], &mut mem);
+    assert!(matches!(
+        beacon.handle_housekeeping_request(&mem[..len]),
+        Err(PusError::MalformedRequest)
+    ));
+    assert!(!beacon.housekeeping.is_enabled());
+    let len = housekeeping_request(
+        MODIFY_COLLECTION_INTERVAL,
+        &[2, 0, 12, 0, 0, 0, 5, 0, 3, 0, 0, 0, 0],
+        &mut mem,
+    );
+    assert!(matches!(
+        beacon.handle_housekeeping_request(&mem[..len]),
+        Err(PusError::MalformedRequest)
+    ));
+    assert_eq!(beacon.housekeeping.interval(), 3);
+
+    let len = housekeeping_request(27, &[1, 0, 12], &mut mem);
+    assert!(matches!(
+        beacon.handle_housekeeping_request(&mem[..len]),
+        Err(PusError::UnsupportedSubtype(27))
+    ));
+}
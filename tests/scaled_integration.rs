@@ -0,0 +1,106 @@
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+type CentiCelsius = Scaled<i16, 100>;
+type CentiKelvin = Scaled<u16, 100, -273>;
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+pub struct Thermal {
+    board: CentiCelsius,
+    battery: CentiKelvin,
+}
+
+#[telemetry_definition(id = 0)]
+mod telemetry {
+    #[tmv(u32)]
+    struct Timestamp;
+    #[tmv(crate::Thermal)]
+    struct Thermal;
+}
+
+beacon!(
+    ThermalBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 2,
+    values(Thermal)
+);
+
+#[test]
+fn scaled_conversion() {
+    let board = CentiCelsius::new(-12.34).unwrap();
+    assert_eq!(board.raw(), -1234);
+    assert!((board.value() + 12.34).abs() < 1e-4);
+
+    let battery = CentiKelvin::new(25.).unwrap();
+    assert_eq!(battery.raw(), 29800);
+    assert_eq!(battery.value(), 25.);
+
+    // raw values outside of the integer range are rejected
+    assert!(matches!(
+        CentiCelsius::new(400.),
+        Err(TMValueError::BadValue)
+    ));
+    assert!(matches!(
+        CentiKelvin::new(-300.),
+        Err(TMValueError::BadValue)
+    ));
+    assert!(matches!(
+        CentiCelsius::new(f32::NAN),
+        Err(TMValueError::BadValue)
+    ));
+}
+
+#[test]
+fn scaled_encoding() {
+    let value = Thermal {
+        board: CentiCelsius::from_raw(-1234),
+        battery: CentiKelvin::new(20.5).unwrap(),
+    };
+    assert_eq!(Thermal::MAX_BYTE_SIZE, 4);
+
+    let mut mem = [0u8; 4];
+    assert_eq!(value.write(&mut mem).unwrap(), 4);
+    assert_eq!(mem[..2], (-1234i16).to_le_bytes());
+    assert_eq!(mem[2..], 29350u16.to_le_bytes());
+    assert_eq!(Thermal::read(&mem).unwrap(), (4, value));
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn scaled_serialization() {
+    use serde_cbor::Value;
+
+    struct CborSerializer;
+    impl ground_tm::Serializer for CborSerializer {
+        type Error = serde_cbor::Error;
+        fn serialize_value<T: serde::Serialize>(
+            &self,
+            value: &T,
+        ) -> Result<std::vec::Vec<u8>, Self::Error> {
+            serde_cbor::to_vec(value)
+        }
+    }
+
+    let mut beacon = ThermalBeacon::new();
+    beacon.thermal = Some(Thermal {
+        board: CentiCelsius::new(21.5).unwrap(),
+        battery: CentiKelvin::new(-3.25).unwrap(),
+    });
+    let serialized = beacon.serialize(&CborSerializer).unwrap();
+    let Value::Map(telemetry) = serde_cbor::from_slice(&serialized[0].1).unwrap() else {
+        panic!("ground telemetry should be a map");
+    };
+    let Value::Map(thermal) = &telemetry[&Value::Text("value".into())] else {
+        panic!("thermal should be a map");
+    };
+    // engineering values, not raw integers
+    assert_eq!(thermal[&Value::Text("board".into())], Value::Float(21.5));
+    assert_eq!(thermal[&Value::Text("battery".into())], Value::Float(-3.25));
+}
@@ -0,0 +1,316 @@
+#![feature(const_trait_impl)]
+#![cfg_attr(feature = "ground", feature(const_cmp))]
+#![cfg(feature = "ground")]
+
+use tmtc_system::schema::{
+    BitOrder, DecodeError, DynamicValue, PackedFieldSchema, Primitive, TypeSchema,
+};
+use tmtc_system::*;
+extern crate alloc;
+
+#[derive(TMValue, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[tm(packed)]
+pub struct Flags {
+    armed: bool,
+    #[tm(bits = 3)]
+    mode: u8,
+    #[tm(bits = 4)]
+    offset: i8,
+}
+
+#[derive(TMValue, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[repr(u16)]
+pub enum Command {
+    Idle = 3,
+    Move { speed: i16 },
+    Wait(#[tm(endian = "big")] u32),
+}
+
+#[derive(TMValue, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Status {
+    flags: Flags,
+    command: Command,
+    samples: [u8; 2],
+    temperature: Option<Scaled<i16, 100>>,
+    counter: BigEndian<u32>,
+    #[tm(skip)]
+    #[serde(skip)]
+    cache: u8,
+}
+
+#[telemetry_definition(id = 0)]
+mod telemetry {
+    #[tmv(u32)]
+    struct Timestamp;
+    #[tmv(crate::Status)]
+    struct Status;
+    #[tmm(id = 10)]
+    mod power {
+        #[tmv(f32)]
+        struct Voltage;
+        #[tmv(char)]
+        struct Unit;
+    }
+}
+
+beacon!(
+    StatusBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 7,
+    values(Status, power::Voltage, power::Unit),
+    crc = crc32c
+);
+
+fn status() -> Status {
+    Status {
+        flags: Flags {
+            armed: true,
+            mode: 5,
+            offset: -3,
+        },
+        command: Command::Wait(0x01020304),
+        samples: [7, 9],
+        temperature: Some(Scaled::new(-12.5).unwrap()),
+        counter: BigEndian(42),
+        cache: 0,
+    }
+}
+
+fn fields(fields: &[(&str, DynamicValue)]) -> Vec<(String, DynamicValue)> {
+    fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+fn status_value() -> DynamicValue {
+    DynamicValue::Struct(fields(&[
+        (
+            "flags",
+            DynamicValue::Struct(fields(&[
+                ("armed", DynamicValue::Bool(true)),
+                ("mode", DynamicValue::Unsigned(5)),
+                ("offset", DynamicValue::Signed(-3)),
+            ])),
+        ),
+        (
+            "command",
+            DynamicValue::Enum {
+                variant: "Wait".into(),
+                fields: fields(&[("0", DynamicValue::Unsigned(0x01020304))]),
+            },
+        ),
+        (
+            "samples",
+            DynamicValue::List(vec![DynamicValue::Unsigned(7), DynamicValue::Unsigned(9)]),
+        ),
+        (
+            "temperature",
+            DynamicValue::Option(Some(Box::new(DynamicValue::Float(-12.5)))),
+        ),
+        ("counter", DynamicValue::Unsigned(42)),
+    ]))
+}
+
+#[test]
+fn derived_schema() {
+    let TypeSchema::Struct { name, fields } = Status::schema() else {
+        panic!("expected a struct schema");
+    };
+    assert_eq!(name, "Status");
+    // skipped fields are not on the wire
+    let names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["flags", "command", "samples", "temperature", "counter"]
+    );
+
+    let TypeSchema::Enum { tag, variants, .. } = Command::schema() else {
+        panic!("expected an enum schema");
+    };
+    assert_eq!(tag, Primitive::Unsigned { bytes: 2 });
+    let tags: Vec<_> = variants.iter().map(|v| (v.name.as_str(), v.tag)).collect();
+    assert_eq!(tags, [("Idle", 3), ("Move", 4), ("Wait", 5)]);
+    assert_eq!(variants[2].fields[0].big_endian, Some(true));
+    assert_eq!(variants[1].fields[0].big_endian, None);
+}
+
+#[test]
+fn dynamic_value_decode() {
+    let value = status();
+    let mut mem = [0u8; Status::MAX_BYTE_SIZE];
+    let len = value.write(&mut mem).unwrap();
+
+    let (decoded_len, decoded) = Status::schema().decode(&mem[..len]).unwrap();
+    assert_eq!(decoded_len, len);
+    assert_eq!(decoded, status_value());
+    assert_eq!(decoded.field("counter"), Some(&DynamicValue::Unsigned(42)));
+
+    assert!(matches!(
+        Status::schema().decode(&mem[..len - 1]),
+        Err(TMValueError::OutOfMemory)
+    ));
+
+    // fields without a byte order of their own follow the surrounding type
+    let command = BigEndian(Command::Move { speed: -2 });
+    let len = command.write(&mut mem).unwrap();
+    assert_eq!(mem[..len], [0, 4, 0xFF, 0xFE]);
+    let (_, decoded) = <BigEndian<Command>>::schema().decode(&mem[..len]).unwrap();
+    assert_eq!(
+        decoded,
+        DynamicValue::Enum {
+            variant: "Move".into(),
+            fields: fields(&[("speed", DynamicValue::Signed(-2))]),
+        }
+    );
+}
+
+#[test]
+fn hostile_schema_decode() {
+    // schemas are loaded by ground tools, bad sizes must not panic the decoder
+    let bytes = [0xFFu8; 32];
+    for bytes_len in [0, 17, 255] {
+        for primitive in [
+            Primitive::Signed { bytes: bytes_len },
+            Primitive::Unsigned { bytes: bytes_len },
+        ] {
+            assert!(matches!(
+                TypeSchema::Primitive(primitive).decode(&bytes),
+                Err(TMValueError::BadValue)
+            ));
+        }
+    }
+    let string = TypeSchema::String {
+        capacity: 8,
+        length_bytes: 0,
+    };
+    assert!(matches!(string.decode(&bytes), Err(TMValueError::BadValue)));
+    for bits in [0, 65, u32::MAX] {
+        let packed = TypeSchema::packed(
+            "Hostile",
+            BitOrder::Msb,
+            [
+                PackedFieldSchema::new("a", 4, Primitive::Signed { bytes: 1 }),
+                PackedFieldSchema::new("b", bits, Primitive::Signed { bytes: 1 }),
+            ],
+        );
+        assert!(matches!(packed.decode(&bytes), Err(TMValueError::BadValue)));
+    }
+}
+
+#[test]
+fn hostile_schema_lengths() {
+    let bytes = [0xFFu8; 32];
+    let u8_schema = || Box::new(TypeSchema::Primitive(Primitive::Unsigned { bytes: 1 }));
+    let array = |len, element| TypeSchema::Array { len, element };
+    let vec = |element| TypeSchema::Vec {
+        capacity: usize::MAX,
+        length_bytes: 8,
+        element,
+    };
+
+    // counts beyond the bytes run out of memory instead of preallocating
+    for schema in [array(usize::MAX, u8_schema()), vec(u8_schema())] {
+        assert!(matches!(
+            schema.decode(&bytes),
+            Err(TMValueError::OutOfMemory)
+        ));
+    }
+    let string = TypeSchema::String {
+        capacity: usize::MAX,
+        length_bytes: 8,
+    };
+    assert!(matches!(
+        string.decode(&bytes),
+        Err(TMValueError::OutOfMemory)
+    ));
+    // zero sized elements take no bytes, huge counts of them are rejected
+    for schema in [
+        array(usize::MAX, Box::new(TypeSchema::Unit)),
+        vec(Box::new(TypeSchema::Unit)),
+    ] {
+        assert!(matches!(schema.decode(&bytes), Err(TMValueError::BadValue)));
+    }
+    assert_eq!(
+        array(4, Box::new(TypeSchema::Unit)).decode(&[]).unwrap(),
+        (0, DynamicValue::List(vec![DynamicValue::Unit; 4]))
+    );
+
+    assert_eq!(array(usize::MAX, u8_schema()).max_size(), Some(usize::MAX));
+    assert_eq!(
+        array(usize::MAX, Box::new(array(2, u8_schema()))).max_size(),
+        None
+    );
+    assert_eq!(vec(u8_schema()).max_size(), None);
+    assert_eq!(Status::schema().max_size(), Some(Status::MAX_BYTE_SIZE));
+}
+
+#[test]
+fn container_decode() {
+    let schema = telemetry::schema();
+    let addresses: Vec<_> = schema
+        .definitions
+        .iter()
+        .map(|d| (d.id, d.address.as_str()))
+        .collect();
+    assert_eq!(
+        addresses,
+        [
+            (0, "telemetry.timestamp"),
+            (1, "telemetry.status"),
+            (10, "telemetry.power.voltage"),
+            (11, "telemetry.power.unit"),
+        ]
+    );
+
+    let container =
+        TelemetryContainer::<{ telemetry::MAX_BYTE_SIZE }>::new(&telemetry::power::Unit, &'V')
+            .unwrap();
+    let decoded = schema
+        .decode_container(container.id(), container.bytes())
+        .unwrap();
+    assert_eq!(decoded.address, "telemetry.power.unit");
+    assert_eq!(decoded.value, DynamicValue::Char('V'));
+
+    assert!(matches!(
+        schema.decode_container(99, container.bytes()),
+        Err(DecodeError::UnknownId(99))
+    ));
+}
+
+#[test]
+fn beacon_decode() {
+    let mut beacon = status_beacon::StatusBeacon::new();
+    beacon.timestamp = 1234;
+    beacon.status = Some(status());
+    beacon.power_unit = Some('A');
+    let bytes = beacon.to_bytes().unwrap().to_vec();
+
+    // ground tools load the schema from a file
+    let file = serde_cbor::to_vec(&status_beacon::StatusBeacon::schema()).unwrap();
+    let schema: schema::BeaconSchema = serde_cbor::from_slice(&file).unwrap();
+    assert_eq!(schema, status_beacon::StatusBeacon::schema());
+
+    let decoded = schema.decode(&bytes).unwrap();
+    assert_eq!(decoded.timestamp, DynamicValue::Unsigned(1234));
+    let values: Vec<_> = decoded
+        .values
+        .iter()
+        .map(|v| (v.address.as_str(), v.value.clone()))
+        .collect();
+    assert_eq!(
+        values,
+        [
+            ("telemetry.status", status_value()),
+            ("telemetry.power.unit", DynamicValue::Char('A')),
+        ]
+    );
+
+    let mut corrupted = bytes.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        schema.decode(&corrupted),
+        Err(DecodeError::BadCRC { .. })
+    ));
+}
@@ -0,0 +1,99 @@
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::space_packet::*;
+use tmtc_system::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+#[telemetry_definition(id = 0x42)]
+mod telemetry {
+    #[tmv(u32)]
+    struct Timestamp;
+    #[tmv(u16)]
+    struct FirstTMValue;
+    #[tmv(i8)]
+    struct SecondTMValue;
+}
+
+beacon!(
+    TestBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 7,
+    values(FirstTMValue, SecondTMValue)
+);
+
+type TestContainer = fd_compat_telemetry_container!(telemetry);
+
+#[test]
+fn primary_header_round_trip() {
+    let header = PrimaryHeader {
+        packet_type: PacketType::Telecommand,
+        secondary_header: true,
+        apid: 0x123,
+        sequence_flags: SequenceFlags::First,
+        sequence_count: 0x2345,
+        data_length: 9,
+    };
+    let mut bytes = [0u8; PRIMARY_HEADER_SIZE];
+    header.write(&mut bytes).unwrap();
+
+    assert_eq!(bytes, [0x19, 0x23, 0x63, 0x45, 0x00, 0x09]);
+    assert_eq!(PrimaryHeader::read(&bytes).unwrap(), header);
+    assert_eq!(header.packet_len(), 16);
+}
+
+#[test]
+fn beacon_space_packet() {
+    let mut beacon = TestBeacon::new();
+    beacon.timestamp = 1000;
+    beacon.first_tm_value = Some(513);
+
+    let mut encoder = SpacePacketEncoder::for_beacon(&beacon);
+    let mut mem = [0u8; 64];
+    let len = encoder.encode_beacon(&mut beacon, true, &mut mem).unwrap();
+    let beacon_len = beacon.to_bytes().unwrap().len();
+    assert_eq!(len, PRIMARY_HEADER_SIZE + 4 + beacon_len);
+    assert_eq!(encoder.sequence_count(), 1);
+
+    let packet = SpacePacket::decode(&mem[..len]).unwrap();
+    assert_eq!(packet.header.apid, 7);
+    assert_eq!(packet.header.sequence_count, 0);
+    assert_eq!(packet.header.sequence_flags, SequenceFlags::Unsegmented);
+    assert!(packet.header.secondary_header);
+
+    let (timestamp, bytes) = packet.split_timestamp::<u32>().unwrap();
+    assert_eq!(timestamp, 1000);
+    let mut received = TestBeacon::new();
+    received.from_bytes(bytes).unwrap();
+    assert_eq!(received.first_tm_value, Some(513));
+    assert_eq!(received.second_tm_value, None);
+}
+
+#[test]
+fn container_space_packet() {
+    let container = TestContainer::new(&telemetry::FirstTMValue, &0xBEEFu16).unwrap();
+    let mut encoder = SpacePacketEncoder::new(telemetry::id_range().0).unwrap();
+    let mut mem = [0u8; 16];
+    let len = encoder.encode_container(&container, &mut mem).unwrap();
+
+    assert_eq!(
+        mem[..len],
+        [0x00, 0x42, 0xC0, 0x00, 0x00, 0x03, 0x43, 0, 0xEF, 0xBE]
+    );
+
+    let packet = SpacePacket::decode(&mem[..len]).unwrap();
+    assert!(!packet.header.secondary_header);
+    assert!(packet.split_timestamp::<u32>().is_err());
+    assert_eq!(packet.split_container().unwrap(), (0x43, &[0xEF, 0xBE][..]));
+
+    assert!(matches!(
+        SpacePacket::decode(&mem[..len - 1]),
+        Err(SpacePacketError::OutOfMemory)
+    ));
+    assert!(matches!(
+        SpacePacketEncoder::new(0x800),
+        Err(SpacePacketError::InvalidApid)
+    ));
+}
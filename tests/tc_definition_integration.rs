@@ -0,0 +1,169 @@
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::*;
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub struct SetHeaterArgs {
+    heater: u8,
+    power: f32,
+}
+
+#[telecommand_definition(id = 0)]
+mod telecommands {
+    #[tcv(u32)]
+    struct Reboot;
+    #[tcv(crate::SetHeaterArgs)]
+    struct SetHeater;
+    #[tcm(id = 100)]
+    mod payload {
+        #[tcv([u8; 4])]
+        struct Upload;
+    }
+}
+
+#[test]
+fn tc_definition_lookup() {
+    assert_eq!(telecommands::id_range(), (0, 101));
+    assert_eq!(telecommands::payload::id_range(), (100, 101));
+    assert_eq!(telecommands::MAX_BYTE_SIZE, 5);
+
+    let set_heater = telecommands::from_id(1).unwrap();
+    assert_eq!(set_heater.address(), "telecommands.set_heater");
+
+    let upload = telecommands::from_address("telecommands.payload.upload").unwrap();
+    assert_eq!(upload.id(), 100);
+
+    assert!(telecommands::from_id(2).is_err());
+}
+
+#[test]
+fn tc_encode_decode() {
+    let args = SetHeaterArgs {
+        heater: 2,
+        power: 1.5,
+    };
+    let mut frame = [0u8; TELECOMMAND_HEADER_SIZE + telecommands::MAX_BYTE_SIZE];
+    let len = telecommands::SetHeater
+        .encode(5, &args, &mut frame)
+        .unwrap();
+
+    assert_eq!(len, 9);
+    assert_eq!(frame[0..2], 1u16.to_le_bytes());
+    assert_eq!(frame[2..4], 5u16.to_le_bytes());
+    assert_eq!(frame[4], 2);
+    assert_eq!(frame[5..9], 1.5f32.to_le_bytes());
+
+    assert_eq!(telecommands::SetHeater.decode(&frame[..len]).unwrap(), args);
+    assert!(matches!(
+        telecommands::Reboot.decode(&frame[..len]),
+        Err(ParseError::WrongId {
+            expected: 0,
+            found: 1
+        })
+    ));
+    let mut long = frame[..len].to_vec();
+    long.push(0);
+    assert!(matches!(
+        telecommands::SetHeater.decode(&long),
+        Err(ParseError::TrailingBytes {
+            expected: 9,
+            found: 10
+        })
+    ));
+}
+
+#[derive(Default)]
+struct TestHandler {
+    reboots: u32,
+    heater: Option<SetHeaterArgs>,
+}
+impl TelecommandHandler<telecommands::Reboot> for TestHandler {
+    fn handle(&mut self, args: u32) -> Result<(), u8> {
+        self.reboots += args;
+        Ok(())
+    }
+}
+impl TelecommandHandler<telecommands::SetHeater> for TestHandler {
+    fn handle(&mut self, args: SetHeaterArgs) -> Result<(), u8> {
+        self.heater = Some(args);
+        Ok(())
+    }
+}
+impl TelecommandHandler<telecommands::payload::Upload> for TestHandler {
+    fn handle(&mut self, _args: [u8; 4]) -> Result<(), u8> {
+        Err(7)
+    }
+}
+
+#[test]
+fn tc_dispatch() {
+    let mut handler = TestHandler::default();
+    let mut frame = [0u8; TELECOMMAND_HEADER_SIZE + telecommands::MAX_BYTE_SIZE];
+
+    let len = telecommands::Reboot.encode(1, &3, &mut frame).unwrap();
+    let header = telecommands::Dispatcher
+        .dispatch(&frame[..len], &mut handler)
+        .unwrap();
+    assert_eq!(header, TelecommandHeader { id: 0, sequence: 1 });
+    assert_eq!(handler.reboots, 3);
+
+    let args = SetHeaterArgs {
+        heater: 1,
+        power: 0.5,
+    };
+    let len = telecommands::SetHeater
+        .encode(2, &args, &mut frame)
+        .unwrap();
+    telecommands::Dispatcher
+        .dispatch(&frame[..len], &mut handler)
+        .unwrap();
+    assert_eq!(handler.heater, Some(args));
+
+    let len = telecommands::payload::Upload
+        .encode(3, &[1, 2, 3, 4], &mut frame)
+        .unwrap();
+    assert!(matches!(
+        telecommands::Dispatcher.dispatch(&frame[..len], &mut handler),
+        Err((
+            Some(TelecommandHeader {
+                id: 100,
+                sequence: 3
+            }),
+            DispatchError::ExecutionFailed(7)
+        ))
+    ));
+}
+
+#[test]
+fn tc_dispatch_rejects_bad_frames() {
+    let mut handler = TestHandler::default();
+
+    assert!(matches!(
+        telecommands::Dispatcher.dispatch(&[0], &mut handler),
+        Err((None, DispatchError::OutOfMemory))
+    ));
+    assert!(matches!(
+        telecommands::Dispatcher.dispatch(&[42, 0, 0, 0, 0, 0], &mut handler),
+        Err((
+            Some(TelecommandHeader {
+                id: 42,
+                sequence: 0
+            }),
+            DispatchError::UnknownId(42)
+        ))
+    ));
+    assert!(matches!(
+        telecommands::Dispatcher.dispatch(&[1, 0, 0, 0, 2], &mut handler),
+        Err((
+            Some(_),
+            DispatchError::BadArguments(TMValueError::OutOfMemory)
+        ))
+    ));
+    // a reboot followed by a byte of another command
+    assert!(matches!(
+        telecommands::Dispatcher.dispatch(&[0, 0, 0, 0, 1, 0, 0, 0, 9], &mut handler),
+        Err((Some(_), DispatchError::TrailingBytes(1)))
+    ));
+    assert_eq!(handler.reboots, 0);
+    assert_eq!(handler.heater, None);
+}
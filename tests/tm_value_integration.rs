@@ -25,6 +25,138 @@ pub enum TestEnum {
     ThirdVar(TestValue),
 }
 
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub enum ModeEnum {
+    #[default]
+    Nominal,
+    Safe {
+        reason: u8,
+        since: u32,
+    },
+    Detumble(i16),
+    Manual {
+        pos: [i16; 2],
+        mem: Option<u8>,
+    },
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub enum SystemState {
+    Boot = 1,
+    #[default]
+    Idle,
+    Science = 10,
+    Downlink,
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+#[repr(i16)]
+pub enum WideEnum {
+    #[default]
+    Low = -300,
+    High(u8) = 300,
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub struct FramedValue {
+    #[tm(const = 0xAA)]
+    sync: u8,
+    value: i32,
+    #[tm(skip)]
+    local_counter: u64,
+    #[tm(const = [1, 2])]
+    trailer: [u8; 2],
+}
+
+#[derive(TMValue, PartialEq, Debug, Clone, Copy)]
+pub enum FramedEnum {
+    Tagged(#[tm(const = 7)] u16, i8),
+    Cached {
+        #[tm(skip)]
+        cache: u32,
+        value: u8,
+    },
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+#[tm(endian = "big")]
+pub struct NetworkOrder {
+    id: u16,
+    samples: [i32; 2],
+    #[tm(endian = "little")]
+    legacy: u16,
+    nested: Option<LittleEndian<u16>>,
+}
+
+#[derive(TMValue, PartialEq, Debug, Clone, Copy)]
+#[repr(u16)]
+#[tm(endian = big)]
+pub enum NetworkEnum {
+    Value(u32) = 0x0102,
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub struct Pair {
+    a: u16,
+    b: u32,
+    #[tm(endian = "little")]
+    legacy: u16,
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub struct FlagTest {
+    armed: bool,
+    mode: char,
+    reserved: (),
+    marker: core::marker::PhantomData<u64>,
+    flags: [bool; 3],
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+#[tm(packed)]
+pub struct PackedStatus {
+    armed: bool,
+    #[tm(bits = 3)]
+    mode: u8,
+    #[tm(bits = 5)]
+    offset: i8,
+    heater: bool,
+    counter: u16,
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+#[tm(packed, bit_order = lsb)]
+pub struct PackedFlags {
+    first: bool,
+    #[tm(bits = 2)]
+    second: u8,
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub struct Millivolts(u16);
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub struct Heartbeat;
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub struct Vec3<T: Copy>(T, T, T);
+
+#[derive(TMValue, PartialEq, Debug, Clone, Copy)]
+pub struct Sample<T, const N: usize>
+where
+    T: Copy,
+{
+    channels: [T; N],
+    heartbeat: Heartbeat,
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+pub enum Reading<T> {
+    #[default]
+    Missing,
+    Value(T),
+}
+
 #[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
 pub struct ArrayTest {
     val: [u32; 5],
@@ -84,3 +216,307 @@ fn tm_value_enums() {
 
     assert_eq!(first_value, first_value_copy);
 }
+
+#[test]
+fn tm_value_named_enums() {
+    assert_eq!(ModeEnum::MAX_BYTE_SIZE, 1 + 2 * 2 + 2);
+
+    let first_value = ModeEnum::Safe {
+        reason: 3,
+        since: 1000,
+    };
+    let first_value_bytes = to_bytes!(ModeEnum, first_value);
+    assert_eq!(first_value_bytes[..6], [1, 3, 0xE8, 0x03, 0, 0]);
+    assert_eq!(
+        ModeEnum::read(&first_value_bytes).unwrap(),
+        (6, first_value)
+    );
+
+    let second_value = ModeEnum::Manual {
+        pos: [-1, 2],
+        mem: Some(9),
+    };
+    let second_value_bytes = to_bytes!(ModeEnum, second_value);
+    assert_eq!(
+        ModeEnum::read(&second_value_bytes).unwrap(),
+        (1 + 4 + 2, second_value)
+    );
+}
+
+#[test]
+fn tm_value_enum_discriminants() {
+    // explicit discriminants are the tag, the others continue counting
+    let tags = [
+        SystemState::Boot,
+        SystemState::Idle,
+        SystemState::Science,
+        SystemState::Downlink,
+    ]
+    .map(|state| to_bytes!(SystemState, state)[0]);
+    assert_eq!(tags, [1, 2, 10, 11]);
+    assert_eq!(SystemState::read(&[10]).unwrap(), (1, SystemState::Science));
+    assert!(matches!(
+        SystemState::read(&[0]),
+        Err(TMValueError::BadEnumVariant)
+    ));
+
+    assert_eq!(WideEnum::MAX_BYTE_SIZE, 2 + 1);
+    let first_value = WideEnum::High(7);
+    let first_value_bytes = to_bytes!(WideEnum, first_value);
+    assert_eq!(first_value_bytes, [0x2C, 0x01, 7]);
+    assert_eq!(
+        WideEnum::read(&first_value_bytes).unwrap(),
+        (3, first_value)
+    );
+    let second_value = WideEnum::Low;
+    let second_value_bytes = to_bytes!(WideEnum, second_value);
+    assert_eq!(second_value_bytes[..2], (-300i16).to_le_bytes());
+    assert_eq!(
+        WideEnum::read(&second_value_bytes).unwrap(),
+        (2, second_value)
+    );
+}
+
+#[test]
+fn tm_value_field_attributes() {
+    assert_eq!(FramedValue::MAX_BYTE_SIZE, 1 + 4 + 2);
+
+    // constants are always written, skipped fields never
+    let first_value = FramedValue {
+        value: -2,
+        local_counter: 99,
+        ..Default::default()
+    };
+    let first_value_bytes = to_bytes!(FramedValue, first_value);
+    assert_eq!(first_value_bytes, [0xAA, 0xFE, 0xFF, 0xFF, 0xFF, 1, 2]);
+    let first_value_copy = FramedValue::read(&first_value_bytes).unwrap().1;
+    assert_eq!(
+        first_value_copy,
+        FramedValue {
+            sync: 0xAA,
+            value: -2,
+            local_counter: 0,
+            trailer: [1, 2],
+        }
+    );
+
+    let mut corrupted = first_value_bytes;
+    corrupted[0] = 0xAB;
+    assert!(matches!(
+        FramedValue::read(&corrupted),
+        Err(TMValueError::BadConstant)
+    ));
+
+    assert_eq!(FramedEnum::MAX_BYTE_SIZE, 1 + 2 + 1);
+    let second_value = FramedEnum::Cached { cache: 5, value: 3 };
+    let second_value_bytes = to_bytes!(FramedEnum, second_value);
+    assert_eq!(second_value_bytes[..2], [1, 3]);
+    assert_eq!(
+        FramedEnum::read(&second_value_bytes).unwrap(),
+        (2, FramedEnum::Cached { cache: 0, value: 3 })
+    );
+    let third_value = FramedEnum::Tagged(0, 4);
+    let third_value_bytes = to_bytes!(FramedEnum, third_value);
+    assert_eq!(third_value_bytes, [0, 7, 0, 4]);
+    assert!(matches!(
+        FramedEnum::read(&[0, 8, 0, 4]),
+        Err(TMValueError::BadConstant)
+    ));
+}
+
+#[test]
+fn tm_value_endianness() {
+    // the default layout stays little endian
+    let first_value = 0x0102u16;
+    assert_eq!(to_bytes!(u16, first_value), [0x02, 0x01]);
+    let second_value = BigEndian(0x0102u16);
+    assert_eq!(to_bytes!(BigEndian<u16>, second_value), [0x01, 0x02]);
+    assert_eq!(
+        <BigEndian<u16>>::read(&[0x01, 0x02]).unwrap(),
+        (2, second_value)
+    );
+
+    let third_value = NetworkOrder {
+        id: 0x0A0B,
+        samples: [1, -1],
+        legacy: 0x0A0B,
+        nested: Some(LittleEndian(0x0A0B)),
+    };
+    let third_value_bytes = to_bytes!(NetworkOrder, third_value);
+    assert_eq!(
+        third_value_bytes,
+        [
+            0x0A, 0x0B, 0, 0, 0, 1, 0xFF, 0xFF, 0xFF, 0xFF, 0x0B, 0x0A, 1, 0x0B, 0x0A
+        ]
+    );
+    assert_eq!(
+        NetworkOrder::read(&third_value_bytes).unwrap(),
+        (15, third_value)
+    );
+
+    let fourth_value = NetworkEnum::Value(3);
+    let fourth_value_bytes = to_bytes!(NetworkEnum, fourth_value);
+    assert_eq!(fourth_value_bytes, [0x01, 0x02, 0, 0, 0, 3]);
+    assert_eq!(
+        NetworkEnum::read(&fourth_value_bytes).unwrap(),
+        (6, fourth_value)
+    );
+}
+
+#[test]
+fn tm_value_derived_endianness() {
+    // derived types follow the byte order of the surrounding type,
+    // fields with a byte order of their own keep it
+    let first_value = BigEndian(Pair {
+        a: 1,
+        b: 2,
+        legacy: 3,
+    });
+    let first_value_bytes = to_bytes!(BigEndian<Pair>, first_value);
+    assert_eq!(first_value_bytes, [0, 1, 0, 0, 0, 2, 3, 0]);
+    assert_eq!(
+        <BigEndian<Pair>>::read(&first_value_bytes).unwrap(),
+        (8, first_value)
+    );
+    let little_endian = first_value.0;
+    assert_eq!(to_bytes!(Pair, little_endian), [1, 0, 2, 0, 0, 0, 3, 0]);
+
+    let second_value = BigEndian(ModeEnum::Safe {
+        reason: 4,
+        since: 0x01020304,
+    });
+    let second_value_bytes = to_bytes!(BigEndian<ModeEnum>, second_value);
+    assert_eq!(second_value_bytes[..6], [1, 4, 1, 2, 3, 4]);
+    assert_eq!(
+        <BigEndian<ModeEnum>>::read(&second_value_bytes).unwrap(),
+        (6, second_value)
+    );
+
+    // the tag follows the byte order too
+    let third_value = BigEndian(WideEnum::High(5));
+    let third_value_bytes = to_bytes!(BigEndian<WideEnum>, third_value);
+    assert_eq!(third_value_bytes, [0x01, 0x2C, 5]);
+    assert_eq!(
+        <BigEndian<WideEnum>>::read(&third_value_bytes).unwrap(),
+        (3, third_value)
+    );
+
+    // an explicit byte order wins over the surrounding one
+    let fourth_value = LittleEndian(NetworkEnum::Value(3));
+    assert_eq!(
+        to_bytes!(LittleEndian<NetworkEnum>, fourth_value),
+        [0x01, 0x02, 0, 0, 0, 3]
+    );
+    // packed layouts are fixed by their bit order
+    let fifth_value = PackedFlags {
+        first: true,
+        second: 2,
+    };
+    let fifth_value_be = BigEndian(fifth_value);
+    assert_eq!(
+        to_bytes!(BigEndian<PackedFlags>, fifth_value_be),
+        to_bytes!(PackedFlags, fifth_value)
+    );
+}
+
+#[test]
+fn tm_value_flags() {
+    let first_value = FlagTest {
+        armed: true,
+        mode: 'ß',
+        flags: [false, true, true],
+        ..Default::default()
+    };
+    let first_value_bytes: [u8; 1 + 4 + 3] = to_bytes!(FlagTest, first_value);
+    let first_value_copy = FlagTest::read(&first_value_bytes).unwrap().1;
+
+    assert_eq!(first_value_bytes, [1, 0xDF, 0, 0, 0, 0, 1, 1]);
+    assert_eq!(first_value, first_value_copy);
+
+    assert!(matches!(bool::read(&[2]), Err(TMValueError::BadValue)));
+    let surrogate = 0xD800u32.to_le_bytes();
+    assert!(matches!(
+        char::read(&surrogate),
+        Err(TMValueError::BadValue)
+    ));
+}
+
+#[test]
+fn tm_value_packed() {
+    let first_value = PackedStatus {
+        armed: true,
+        mode: 5,
+        offset: -3,
+        heater: true,
+        counter: 0xABCD,
+    };
+    // 1 + 3 + 5 + 1 + 16 bits
+    let first_value_bytes: [u8; 4] = to_bytes!(PackedStatus, first_value);
+    let first_value_copy = PackedStatus::read(&first_value_bytes).unwrap().1;
+
+    assert_eq!(
+        first_value_bytes,
+        [0b1101_1110, 0b1110_1010, 0b1111_0011, 0b0100_0000]
+    );
+    assert_eq!(first_value, first_value_copy);
+
+    let second_value = PackedFlags {
+        first: true,
+        second: 2,
+    };
+    let second_value_bytes: [u8; 1] = to_bytes!(PackedFlags, second_value);
+    assert_eq!(second_value_bytes, [0b101]);
+    assert_eq!(
+        PackedFlags::read(&second_value_bytes).unwrap().1,
+        second_value
+    );
+
+    // values exceeding their bit width and set padding bits are rejected
+    let mut mem = [0u8; 4];
+    let too_wide = PackedStatus {
+        mode: 8,
+        ..first_value
+    };
+    assert!(matches!(
+        too_wide.write(&mut mem),
+        Err(TMValueError::BadValue)
+    ));
+    assert!(matches!(
+        PackedFlags::read(&[0b1000]),
+        Err(TMValueError::BadValue)
+    ));
+}
+
+#[test]
+fn tm_value_tuple_and_generic_structs() {
+    let first_value = Millivolts(3300);
+    let first_value_bytes = to_bytes!(Millivolts, first_value);
+    assert_eq!(first_value_bytes, 3300u16.to_le_bytes());
+    assert_eq!(
+        Millivolts::read(&first_value_bytes).unwrap(),
+        (2, first_value)
+    );
+
+    assert_eq!(Heartbeat::MAX_BYTE_SIZE, 0);
+    assert_eq!(Heartbeat::read(&[]).unwrap(), (0, Heartbeat));
+
+    let second_value = Vec3(Millivolts(1), Millivolts(2), Millivolts(3));
+    let second_value_bytes = to_bytes!(Vec3<Millivolts>, second_value);
+    assert_eq!(second_value_bytes, [1, 0, 2, 0, 3, 0]);
+    assert_eq!(
+        <Vec3<Millivolts>>::read(&second_value_bytes).unwrap(),
+        (6, second_value)
+    );
+
+    let third_value = Sample {
+        channels: [Reading::Value(-1.5f32), Reading::Missing],
+        heartbeat: Heartbeat,
+    };
+    type ThirdType = Sample<Reading<f32>, 2>;
+    assert_eq!(ThirdType::MAX_BYTE_SIZE, 2 * (1 + 4));
+    let third_value_bytes = to_bytes!(ThirdType, third_value);
+    assert_eq!(
+        ThirdType::read(&third_value_bytes).unwrap(),
+        (6, third_value)
+    );
+}
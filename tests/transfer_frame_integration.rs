@@ -0,0 +1,181 @@
+use tmtc_system::TransferFrameError;
+use tmtc_system::space_packet::*;
+use tmtc_system::transfer_frame::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+const FRAME_LEN: usize = 32;
+
+fn packet(encoder: &mut SpacePacketEncoder, len: usize) -> Vec<u8> {
+    let user_data: Vec<u8> = (0..len as u8).collect();
+    let mut mem = vec![0u8; PRIMARY_HEADER_SIZE + len];
+    encoder.encode(&[], &user_data, &mut mem).unwrap();
+    mem
+}
+
+#[test]
+fn frames_span_packets() {
+    let mut builder = TransferFrameBuilder::<FRAME_LEN, 2>::new(0x1AB, true).unwrap();
+    let mut encoder = SpacePacketEncoder::new(5).unwrap();
+    let mut frames: Vec<Vec<u8>> = Vec::new();
+
+    // 30 byte packet followed by a 10 byte one, 24 bytes of data per frame
+    let first = packet(&mut encoder, 24);
+    let second = packet(&mut encoder, 4);
+    builder
+        .push_packet(1, &first, &mut |f| frames.push(f.to_vec()))
+        .unwrap();
+    builder
+        .push_packet(1, &second, &mut |f| frames.push(f.to_vec()))
+        .unwrap();
+    assert_eq!(frames.len(), 1);
+    builder.flush(1, &mut |f| frames.push(f.to_vec())).unwrap();
+    assert_eq!(frames.len(), 2);
+
+    let header = FrameHeader::read(&frames[0]).unwrap();
+    assert_eq!(header.spacecraft_id, 0x1AB);
+    assert_eq!(header.virtual_channel, 1);
+    assert_eq!(header.first_header_pointer, 0);
+    let header = FrameHeader::read(&frames[1]).unwrap();
+    assert_eq!(header.master_frame_count, 1);
+    assert_eq!(header.virtual_frame_count, 1);
+    assert_eq!(header.first_header_pointer, 6);
+    assert_eq!(
+        frames[1][FRAME_HEADER_SIZE + 16..FRAME_HEADER_SIZE + 18],
+        [0x07, 0xFF]
+    );
+
+    assert!(matches!(
+        builder.push_packet(2, &first, &mut |_| {}),
+        Err(TransferFrameError::InvalidVirtualChannel)
+    ));
+    assert!(matches!(
+        builder.push_packet(0, &first[..10], &mut |_| {}),
+        Err(TransferFrameError::InvalidPacket)
+    ));
+}
+
+#[test]
+fn idle_packet_spans_frames() {
+    let mut builder = TransferFrameBuilder::<FRAME_LEN, 1>::new(1, false).unwrap();
+    let mut encoder = SpacePacketEncoder::new(5).unwrap();
+    let mut frames: Vec<Vec<u8>> = Vec::new();
+
+    // leaves 3 bytes, too little for an idle packet
+    builder
+        .push_packet(0, &packet(&mut encoder, 17), &mut |f| {
+            frames.push(f.to_vec())
+        })
+        .unwrap();
+    builder.flush(0, &mut |f| frames.push(f.to_vec())).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(
+        FrameHeader::read(&frames[1]).unwrap().first_header_pointer,
+        NO_PACKET_START
+    );
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn demultiplex_frames() {
+    let mut builder = TransferFrameBuilder::<FRAME_LEN, 2>::new(3, true).unwrap();
+    let mut encoder = SpacePacketEncoder::new(5).unwrap();
+    let mut frames: Vec<Vec<u8>> = Vec::new();
+
+    let packets: Vec<_> = [40, 3, 12, 1]
+        .iter()
+        .map(|l| packet(&mut encoder, *l))
+        .collect();
+    for (i, p) in packets.iter().enumerate() {
+        builder
+            .push_packet(i as u8 % 2, p, &mut |f| frames.push(f.to_vec()))
+            .unwrap();
+    }
+    builder.flush(0, &mut |f| frames.push(f.to_vec())).unwrap();
+    builder.flush(1, &mut |f| frames.push(f.to_vec())).unwrap();
+
+    let mut demultiplexer = TransferFrameDemultiplexer::new(FRAME_LEN, true).unwrap();
+    let mut received = Vec::new();
+    for frame in &frames {
+        let (_, mut packets) = demultiplexer.push_frame(frame).unwrap();
+        received.append(&mut packets);
+    }
+    assert_eq!(received.len(), 4);
+    for p in &packets {
+        assert!(received.contains(p));
+    }
+
+    let mut corrupted = frames[0].clone();
+    corrupted[10] ^= 1;
+    assert!(matches!(
+        demultiplexer.push_frame(&corrupted),
+        Err(TransferFrameError::BadCRC)
+    ));
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn demultiplexer_resyncs_after_frame_loss() {
+    let mut builder = TransferFrameBuilder::<FRAME_LEN, 1>::new(3, false).unwrap();
+    let mut encoder = SpacePacketEncoder::new(5).unwrap();
+    let mut frames: Vec<Vec<u8>> = Vec::new();
+
+    let spanning = packet(&mut encoder, 50);
+    let last = packet(&mut encoder, 2);
+    for p in [&spanning, &last] {
+        builder
+            .push_packet(0, p, &mut |f| frames.push(f.to_vec()))
+            .unwrap();
+    }
+    builder.flush(0, &mut |f| frames.push(f.to_vec())).unwrap();
+
+    let mut demultiplexer = TransferFrameDemultiplexer::new(FRAME_LEN, false).unwrap();
+    let mut received = Vec::new();
+    for frame in frames.iter().skip(1) {
+        let (_, mut packets) = demultiplexer.push_frame(frame).unwrap();
+        received.append(&mut packets);
+    }
+    assert_eq!(received, [last]);
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn demultiplexer_keeps_packets_before_malformed_header() {
+    let mut builder = TransferFrameBuilder::<FRAME_LEN, 1>::new(3, false).unwrap();
+    let mut encoder = SpacePacketEncoder::new(5).unwrap();
+    let mut frames: Vec<Vec<u8>> = Vec::new();
+
+    let packets: Vec<_> = [4, 4, 20, 4]
+        .iter()
+        .map(|l| packet(&mut encoder, *l))
+        .collect();
+    for p in &packets {
+        builder
+            .push_packet(0, p, &mut |f| frames.push(f.to_vec()))
+            .unwrap();
+    }
+    builder.flush(0, &mut |f| frames.push(f.to_vec())).unwrap();
+
+    // unsupported version in the header of the second packet
+    frames[0][FRAME_HEADER_SIZE + packets[0].len()] |= 0xE0;
+    let mut demultiplexer = TransferFrameDemultiplexer::new(FRAME_LEN, false).unwrap();
+    let mut received = Vec::new();
+    for frame in &frames {
+        let (_, mut packets) = demultiplexer.push_frame(frame).unwrap();
+        received.append(&mut packets);
+    }
+    assert_eq!(received, [packets[0].clone(), packets[3].clone()]);
+}
+
+#[cfg(feature = "ground")]
+#[test]
+fn demultiplexer_rejects_short_frames() {
+    assert!(matches!(
+        TransferFrameDemultiplexer::new(FRAME_HEADER_SIZE + FECF_SIZE, true),
+        Err(TransferFrameError::InvalidLength)
+    ));
+    assert!(
+        TransferFrameDemultiplexer::new(FRAME_HEADER_SIZE + PRIMARY_HEADER_SIZE + 1, false).is_ok()
+    );
+}
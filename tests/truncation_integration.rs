@@ -0,0 +1,130 @@
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+pub struct TestVector {
+    x: i16,
+    y: Option<f32>,
+    z: [u8; 3],
+}
+
+#[derive(TMValue, Default, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "ground", derive(serde::Serialize))]
+#[cfg_attr(feature = "ground-deserialize", derive(serde::Deserialize))]
+pub enum TestEnum {
+    #[default]
+    Empty,
+    Vector(TestVector, u16),
+}
+
+#[telemetry_definition(id = 0)]
+mod telemetry {
+    #[tmv(u64)]
+    struct Timestamp;
+    #[tmv(crate::TestEnum)]
+    struct EnumValue;
+    #[tmv([Option<u16>; 2])]
+    struct ArrayValue;
+}
+
+beacon!(
+    TestBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 3,
+    values(EnumValue, ArrayValue)
+);
+
+/// Every strict prefix of a valid encoding has to be rejected without panicking
+fn assert_truncations_rejected<T: TMValue + PartialEq + core::fmt::Debug>(value: T) {
+    let mut mem = [0u8; 64];
+    let len = value.write(&mut mem).unwrap();
+    for i in 0..len {
+        assert!(matches!(T::read(&mem[..i]), Err(TMValueError::OutOfMemory)));
+        assert!(matches!(
+            value.write(&mut [0u8; 64][..i]),
+            Err(TMValueError::OutOfMemory)
+        ));
+    }
+    assert_eq!(T::read(&mem[..len]).unwrap(), (len, value));
+}
+
+#[test]
+fn truncated_values() {
+    let vector = TestVector {
+        x: -3,
+        y: Some(1.5),
+        z: [1, 2, 3],
+    };
+    assert_truncations_rejected(0x12345678u32);
+    assert_truncations_rejected(Some(7i64));
+    assert_truncations_rejected([Some(1u16), None, Some(3)]);
+    assert_truncations_rejected(vector);
+    assert_truncations_rejected(TestEnum::Vector(vector, 9));
+}
+
+#[test]
+fn hostile_tags() {
+    for tag in 2..=u8::MAX {
+        assert!(matches!(
+            Option::<u8>::read(&[tag, 0]),
+            Err(TMValueError::BadEnumVariant)
+        ));
+        assert!(matches!(
+            TestEnum::read(&[tag; 32]),
+            Err(TMValueError::BadEnumVariant)
+        ));
+    }
+}
+
+#[test]
+fn truncated_beacons() {
+    let mut beacon = TestBeacon::new();
+    beacon.timestamp = 99;
+    beacon.enum_value = Some(TestEnum::Vector(TestVector::default(), 1));
+    beacon.array_value = Some([None, Some(4)]);
+    let bytes = beacon.to_bytes().unwrap();
+
+    let mut received = TestBeacon::new();
+    for i in 0..bytes.len() {
+        assert!(received.from_bytes(&bytes[..i]).is_err());
+        assert_eq!(received.timestamp, 0);
+        assert_eq!(received.enum_value, None);
+    }
+    received.from_bytes(bytes).unwrap();
+    assert_eq!(received.array_value, Some([None, Some(4)]));
+}
+
+#[test]
+fn hostile_beacons() {
+    let mut beacon = TestBeacon::new();
+    beacon.enum_value = Some(TestEnum::Empty);
+    let mut bytes = [0u8; TestBeacon::BYTE_SIZE];
+    let len = {
+        let valid = beacon.to_bytes().unwrap();
+        bytes[..valid.len()].copy_from_slice(valid);
+        valid.len()
+    };
+
+    // claim every value is present while the payload ends early
+    bytes[3] = 0b11;
+    let crc = crc::ccitt16(&bytes[3..len]);
+    bytes[1..3].copy_from_slice(&crc.to_le_bytes());
+
+    let mut received = TestBeacon::new();
+    assert!(matches!(
+        received.from_bytes(&bytes[..len]),
+        Err(ParseError::InvalidValue {
+            address: "telemetry.array_value",
+            available: 0,
+            error: TMValueError::OutOfMemory,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,114 @@
+#![feature(const_trait_impl)]
+#![feature(const_cmp)]
+use tmtc_system::*;
+
+#[cfg(feature = "ground")]
+extern crate alloc;
+
+use tmtc_system::verification;
+
+#[telemetry_definition(id = 0)]
+mod telemetry {
+    #[tmv(i64)]
+    struct Timestamp;
+    #[tmv(u32)]
+    struct FirstTMValue;
+}
+
+#[telecommand_definition(id = 0)]
+mod telecommands {
+    #[tcv(u8)]
+    struct Deploy;
+}
+
+beacon!(
+    ReportBeacon,
+    crate,
+    crate::telemetry::Timestamp,
+    id = 1,
+    values(
+        telemetry::FirstTMValue,
+        verification::AcceptanceFailure,
+        verification::CompletionSuccess
+    )
+);
+
+type VerificationContainer = fd_compat_telemetry_container!(verification);
+
+struct DeployHandler;
+impl TelecommandHandler<telecommands::Deploy> for DeployHandler {
+    fn handle(&mut self, _args: u8) -> Result<(), u8> {
+        Ok(())
+    }
+}
+
+#[test]
+fn verification_definitions() {
+    assert_eq!(verification::id_range(), (0xFF00, 0xFF07));
+    assert_eq!(
+        verification::from_id(0xFF06).unwrap().address(),
+        "verification.completion_failure"
+    );
+    assert_eq!(verification::MAX_BYTE_SIZE, 2 + 2 + 2);
+
+    let report = FailureReport::new(3, 12, FailureCode::ExecutionFailed(4));
+    let container = VerificationContainer::new(&verification::CompletionFailure, &report).unwrap();
+    assert_eq!(container.id(), 0xFF06);
+    assert_eq!(container.bytes(), [3, 0, 12, 0, 3, 4]);
+}
+
+#[test]
+fn verification_in_beacon() {
+    let mut beacon = ReportBeacon::new();
+    let mut handler = DeployHandler;
+
+    let mut frame = [0u8; TELECOMMAND_HEADER_SIZE + telecommands::MAX_BYTE_SIZE];
+    let len = telecommands::Deploy.encode(1, &1, &mut frame).unwrap();
+    let header = telecommands::Dispatcher
+        .dispatch(&frame[..len], &mut handler)
+        .unwrap();
+    beacon.verification_completion_success = Some(ExecutionReport::for_command(&header));
+
+    let (header, error) = telecommands::Dispatcher
+        .dispatch(&[9, 0, 2, 0], &mut handler)
+        .unwrap_err();
+    let code = FailureCode::from(&error);
+    assert!(code.is_acceptance_failure());
+    let header = header.unwrap();
+    beacon.verification_acceptance_failure = Some(FailureReport::for_command(&header, code));
+
+    beacon
+        .insert_slice(&verification::CompletionSuccess, &[0, 0, 3, 0])
+        .unwrap();
+    assert_eq!(
+        beacon.verification_completion_success,
+        Some(ExecutionReport::new(0, 3))
+    );
+    assert_eq!(
+        beacon.verification_acceptance_failure,
+        Some(FailureReport::new(9, 2, FailureCode::UnknownCommand))
+    );
+}
+
+#[test]
+fn reports_echo_sequence() {
+    let mut handler = DeployHandler;
+    let mut first = [0u8; TELECOMMAND_HEADER_SIZE + telecommands::MAX_BYTE_SIZE];
+    let mut second = first;
+    let first_len = telecommands::Deploy.encode(41, &1, &mut first).unwrap();
+    let second_len = telecommands::Deploy.encode(42, &2, &mut second).unwrap();
+
+    // both acks reference the same command, only the sequence count tells them apart
+    let second_ack = ExecutionReport::for_command(
+        &telecommands::Dispatcher
+            .dispatch(&second[..second_len], &mut handler)
+            .unwrap(),
+    );
+    let first_ack = ExecutionReport::for_command(
+        &telecommands::Dispatcher
+            .dispatch(&first[..first_len], &mut handler)
+            .unwrap(),
+    );
+    assert_eq!(first_ack, ExecutionReport::new(0, 41));
+    assert_eq!(second_ack, ExecutionReport::new(0, 42));
+}
@@ -0,0 +1,151 @@
+#![feature(const_trait_impl)]
+#![cfg_attr(feature = "ground", feature(const_cmp))]
+#![cfg(feature = "ground")]
+
+use tmtc_system::*;
+extern crate alloc;
+
+#[derive(TMValue, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Mode {
+    Safe,
+    Nominal,
+}
+
+#[derive(TMValue, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Attitude {
+    quaternion: [f32; 4],
+    mode: Mode,
+    temperature: Scaled<i16, 100>,
+}
+
+fn to_celsius(value: &BigEndian<u16>) -> f32 {
+    value.0 as f32 / 10.
+}
+
+#[telemetry_definition(id = 0)]
+mod telemetry {
+    #[tmv(u32)]
+    struct Timestamp;
+    #[tmv(crate::Attitude)]
+    struct Attitude;
+    #[tmm(id = 10)]
+    mod thermal {
+        #[tmv(u16, endian = big, c = crate::to_celsius)]
+        struct Panel;
+    }
+}
+
+beacon!(
+    AocsBeacon,
+    crate::telemetry,
+    crate::telemetry::Timestamp,
+    id = 2,
+    values(Attitude, thermal::Panel)
+);
+
+fn xtce() -> String {
+    xtce::space_system(&telemetry::schema(), &[AocsBeacon::schema()])
+}
+
+#[test]
+fn parameters_in_module_space_systems() {
+    let xml = xtce();
+    assert!(xml.contains(
+        "<SpaceSystem xmlns=\"http://www.omg.org/spec/XTCE/20180204\" name=\"telemetry\">"
+    ));
+    assert!(xml.contains("<SpaceSystem name=\"thermal\">"));
+    assert!(xml.contains("<Parameter name=\"attitude\" parameterTypeRef=\"/telemetry/Attitude\">"));
+    assert!(xml.contains("<Parameter name=\"panel\" parameterTypeRef=\"/telemetry/u16_be\">"));
+    // calibrations are listed with their function
+    assert!(
+        xml.contains("<AncillaryData name=\"calibration.c\">crate :: to_celsius</AncillaryData>")
+    );
+}
+
+#[test]
+fn parameter_types() {
+    let xml = xtce();
+    assert!(xml.contains("<Member name=\"quaternion\" typeRef=\"/telemetry/f32_le_array_4\"/>"));
+    assert!(xml.contains("<EnumeratedParameterType name=\"Mode\">"));
+    assert!(xml.contains("<Enumeration value=\"1\" label=\"Nominal\"/>"));
+    assert!(xml.contains(
+        "<IntegerDataEncoding sizeInBits=\"16\" encoding=\"unsigned\" byteOrder=\"mostSignificantByteFirst\"/>"
+    ));
+    // scaled values are calibrated by a polynomial
+    assert!(xml.contains(
+        "<Term coefficient=\"0\" exponent=\"0\"/><Term coefficient=\"0.01\" exponent=\"1\"/>"
+    ));
+}
+
+#[test]
+fn beacon_container() {
+    let xml = xtce();
+    assert!(xml.contains("<SequenceContainer name=\"AocsBeacon\">"));
+    assert!(xml.contains("<FixedValueEntry name=\"id\" binaryValue=\"02\" sizeInBits=\"8\"/>"));
+    assert!(xml.contains("<ParameterRefEntry parameterRef=\"/telemetry/timestamp\"/>"));
+    assert!(xml.contains(
+        "<ParameterRefEntry parameterRef=\"/telemetry/thermal/panel\"><IncludeCondition>\
+         <Comparison parameterRef=\"AocsBeacon_present_1\" value=\"1\"/>"
+    ));
+    // the presence bits of the first bitfield byte are read most significant first
+    let first = xml.find("parameterRef=\"AocsBeacon_present_7\"/>").unwrap();
+    let last = xml.find("parameterRef=\"AocsBeacon_present_0\"/>").unwrap();
+    assert!(first < last);
+}
+
+beacon!(
+    MixedBeacon,
+    crate,
+    crate::telemetry::Timestamp,
+    id = 3,
+    values(telemetry::thermal::Panel, verification::CompletionSuccess)
+);
+
+/// Absolute paths of the declared parameters and every parameter reference
+fn parameters_and_refs(xml: &str) -> (Vec<String>, Vec<String>) {
+    let mut systems = Vec::new();
+    let mut parameters = Vec::new();
+    let mut refs = Vec::new();
+    let attribute = |line: &str, name: &str| {
+        line.split(&format!(" {}=\"", name))
+            .skip(1)
+            .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+            .collect::<Vec<_>>()
+    };
+    for line in xml.lines().map(str::trim) {
+        if line.starts_with("<SpaceSystem") {
+            systems.push(attribute(line, "name").remove(0));
+        } else if line == "</SpaceSystem>" {
+            systems.pop();
+        } else if line.starts_with("<Parameter ") {
+            let name = attribute(line, "name").remove(0);
+            parameters.push(format!("/{}/{}", systems.join("/"), name));
+        }
+        for reference in attribute(line, "parameterRef") {
+            // relative references are resolved in the root space system
+            refs.push(match reference.starts_with('/') {
+                true => reference,
+                false => format!("/{}/{}", systems[0], reference),
+            });
+        }
+    }
+    (parameters, refs)
+}
+
+#[test]
+fn mixed_tree_beacon() {
+    let xml = xtce::space_system(&telemetry::schema(), &[MixedBeacon::schema()]);
+    assert!(xml.contains("<SpaceSystem name=\"verification\">"));
+    assert!(xml.contains(
+        "<ParameterRefEntry parameterRef=\"/telemetry/verification/completion_success\">"
+    ));
+    let (parameters, refs) = parameters_and_refs(&xml);
+    assert!(refs.len() > 2);
+    for reference in refs {
+        assert!(
+            parameters.contains(&reference),
+            "{} is not declared",
+            reference
+        );
+    }
+}